@@ -0,0 +1,47 @@
+// Embeds the original exercises into the binary, so `rustlings reset` can
+// restore them however much the student changed since. See src/originals.rs.
+//
+// They come from the snapshot in `.rustlings-originals`, not from `exercises`:
+// students build rustlings in their own checkout, where the exercises hold
+// their solutions. Every file of the snapshot is checked against its hash in
+// `.rustlings-originals/SHA256SUMS`, so a snapshot that was edited by accident
+// fails the build instead of becoming the originals.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const SNAPSHOT_DIR: &str = ".rustlings-originals";
+
+fn main() {
+    let root = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let snapshot = root.join(SNAPSHOT_DIR);
+    let sums = fs::read_to_string(snapshot.join("SHA256SUMS"))
+        .unwrap_or_else(|e| panic!("Failed to read {SNAPSHOT_DIR}/SHA256SUMS: {e}"));
+
+    let mut generated = String::from("pub static ORIGINALS: &[(&str, &str)] = &[\n");
+    for line in sums.lines().filter(|line| !line.trim().is_empty()) {
+        let Some((expected, path)) = line.split_once("  ") else {
+            panic!("{SNAPSHOT_DIR}/SHA256SUMS has a line without a hash and a path: {line}");
+        };
+        let file = snapshot.join(path);
+        let contents =
+            fs::read(&file).unwrap_or_else(|e| panic!("Failed to read {SNAPSHOT_DIR}/{path}: {e}"));
+        if hex(&hmac_sha256::Hash::hash(&contents)) != expected {
+            panic!(
+                "{SNAPSHOT_DIR}/{path} doesn't match its hash in SHA256SUMS, \
+                 restore it with `git checkout {SNAPSHOT_DIR}`"
+            );
+        }
+        generated += &format!("    ({path:?}, include_str!({:?})),\n", file.display());
+    }
+    generated += "];\n";
+
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out.join("originals.rs"), generated).unwrap();
+    println!("cargo:rerun-if-changed={SNAPSHOT_DIR}");
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
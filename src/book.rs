@@ -0,0 +1,99 @@
+// The sections of the Rust Book that exercises refer to in info.toml.
+//
+// The numbering is the one of the book before the 2024 edition, which the
+// exercises were written against, so the links go to that version of it.
+
+use crate::exercise::Exercise;
+use crate::reporter::reporter;
+use console::style;
+
+const BOOK_URL: &str = "https://doc.rust-lang.org/1.70.0/book";
+
+// Section number, title and page of every section referred to in info.toml
+#[rustfmt::skip]
+const SECTIONS: &[(&str, &str, &str)] = &[
+    ("3.1", "Variables and Mutability", "ch03-01-variables-and-mutability.html"),
+    ("3.2", "Data Types", "ch03-02-data-types.html"),
+    ("3.3", "Functions", "ch03-03-how-functions-work.html"),
+    ("3.5", "Control Flow", "ch03-05-control-flow.html"),
+    ("4.1", "What Is Ownership?", "ch04-01-what-is-ownership.html"),
+    ("4.2", "References and Borrowing", "ch04-02-references-and-borrowing.html"),
+    ("4.3", "The Slice Type", "ch04-03-slices.html"),
+    ("5.1", "Defining and Instantiating Structs", "ch05-01-defining-structs.html"),
+    ("5.3", "Method Syntax", "ch05-03-method-syntax.html"),
+    ("6", "Enums and Pattern Matching", "ch06-00-enums.html"),
+    (
+        "7",
+        "Managing Growing Projects with Packages, Crates, and Modules",
+        "ch07-00-managing-growing-projects-with-packages-crates-and-modules.html",
+    ),
+    ("8.1", "Storing Lists of Values with Vectors", "ch08-01-vectors.html"),
+    ("8.2", "Storing UTF-8 Encoded Text with Strings", "ch08-02-strings.html"),
+    ("8.3", "Storing Keys with Associated Values in Hash Maps", "ch08-03-hash-maps.html"),
+    ("9", "Error Handling", "ch09-00-error-handling.html"),
+    ("10", "Generic Types, Traits, and Lifetimes", "ch10-00-generics.html"),
+    ("10.1", "Generic Data Types", "ch10-01-syntax.html"),
+    ("10.2", "Traits: Defining Shared Behavior", "ch10-02-traits.html"),
+    ("10.3", "Validating References with Lifetimes", "ch10-03-lifetime-syntax.html"),
+    ("11.1", "How to Write Tests", "ch11-01-writing-tests.html"),
+    ("13.2", "Processing a Series of Items with Iterators", "ch13-02-iterators.html"),
+    ("13.3", "Improving Our I/O Project", "ch13-03-improving-our-io-project.html"),
+    ("13.4", "Comparing Performance: Loops vs. Iterators", "ch13-04-performance.html"),
+    ("15", "Smart Pointers", "ch15-00-smart-pointers.html"),
+    ("16.1", "Using Threads to Run Code Simultaneously", "ch16-01-threads.html"),
+    ("16.2", "Using Message Passing to Transfer Data Between Threads", "ch16-02-message-passing.html"),
+    ("16.3", "Shared-State Concurrency", "ch16-03-shared-state.html"),
+    ("18.3", "Pattern Syntax", "ch18-03-pattern-syntax.html"),
+    ("19.6", "Macros", "ch19-06-macros.html"),
+    ("21.4", "Appendix D - Useful Development Tools", "appendix-04-useful-development-tools.html"),
+];
+
+// Print the sections of the book that belong to the exercise
+pub fn print_sections(exercise: &Exercise) {
+    if exercise.book_chapters.is_empty() {
+        reporter().info(&format!(
+            "There are no chapters of the Rust Book listed for {}.",
+            exercise.name
+        ));
+        reporter().info(&format!("The whole book is at {BOOK_URL}/"));
+        return;
+    }
+    reporter().info(&format!(
+        "{} is about these sections of the Rust Book:",
+        exercise.name
+    ));
+    for chapter in &exercise.book_chapters {
+        match section(chapter) {
+            Some((title, page)) => {
+                reporter().info(&format!("  §{chapter} {}", style(title).bold()));
+                reporter().info(&format!("      {BOOK_URL}/{page}"));
+            }
+            None => reporter().info(&format!("  §{chapter}")),
+        }
+    }
+}
+
+fn section(number: &str) -> Option<(&'static str, &'static str)> {
+    SECTIONS
+        .iter()
+        .find(|(n, _, _)| *n == number.trim_start_matches('§'))
+        .map(|&(_, title, page)| (title, page))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_section() {
+        assert_eq!(
+            section("4.1"),
+            Some(("What Is Ownership?", "ch04-01-what-is-ownership.html"))
+        );
+        assert_eq!(
+            section("§6").map(|(title, _)| title),
+            Some("Enums and Pattern Matching")
+        );
+        assert_eq!(section("99.9"), None);
+    }
+}
@@ -0,0 +1,165 @@
+// An on-disk cache of what compiling and running an exercise came to.
+//
+// `watch` verifies every pending exercise again whenever a file is saved,
+// even though usually only one of them changed. Results are stored under
+// `.rustlings/cache`, keyed by everything that decides them: the exercise's
+// source files, its mode, the rustc version, the flags it's built with and
+// whether it runs in a sandbox.
+// Anything that changes one of those simply ends up with a different key.
+
+use crate::digest::sha256_hex;
+use crate::exercise::{self, Exercise, ExerciseOutput};
+use crate::sandbox;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const CACHE_DIR: &str = ".rustlings/cache";
+// Bump this whenever the format of the entries or the way they are computed changes
+const CACHE_VERSION: u32 = 3;
+
+// What compiling (and running) an exercise came to
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    // The exercise didn't compile, with the compiler's output
+    CompileError(ExerciseOutput),
+    // The exercise compiled, but running it failed
+    RunError(ExerciseOutput),
+    // The exercise compiled and, unless it's only compiled, ran successfully
+    Success(ExerciseOutput),
+    // Compiling or running the exercise was stopped before it finished
+    Cancelled,
+}
+
+impl Outcome {
+    // Whether the same source is certain to come to the same outcome again.
+    // Running into the timeout or crashing can depend on the load of the machine.
+    fn is_reproducible(&self) -> bool {
+        match self {
+            Outcome::Success(_) => true,
+            Outcome::CompileError(output) => !output.timed_out,
+            Outcome::RunError(output) => !output.timed_out && !output.crashed,
+            Outcome::Cancelled => false,
+        }
+    }
+}
+
+// Look up the outcome of the exercise in the cache, or work it out with
+// `evaluate` and remember it. Failing to read or write the cache is never an
+// error, the exercise is just evaluated again.
+pub fn cached(exercise: &Exercise, evaluate: impl FnOnce() -> Outcome) -> Outcome {
+    let Some(entry) = entry_path(exercise) else {
+        return evaluate();
+    };
+    if let Some(outcome) = fs::read_to_string(&entry)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+    {
+        return outcome;
+    }
+
+    let outcome = evaluate();
+    if outcome.is_reproducible() {
+        let _ignored = store(&entry, &outcome);
+    }
+    outcome
+}
+
+// Remove all cached results
+pub fn clear() -> io::Result<()> {
+    match fs::remove_dir_all(CACHE_DIR) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn store(entry: &Path, outcome: &Outcome) -> io::Result<()> {
+    fs::create_dir_all(CACHE_DIR)?;
+    // Write to a temporary file first, so that an interrupted write
+    // never leaves a truncated entry behind
+    let temp = entry.with_extension("tmp");
+    fs::write(&temp, serde_json::to_string(outcome)?)?;
+    fs::rename(&temp, entry)
+}
+
+// The file the outcome of the exercise in its current state is cached in
+fn entry_path(exercise: &Exercise) -> Option<PathBuf> {
+    let key = key(exercise, sandbox::enabled())?;
+    Some(Path::new(CACHE_DIR).join(format!("{key}.json")))
+}
+
+// An exercise can pass without a sandbox and fail in one (e.g. if it writes
+// files or uses the network), so the two are cached separately
+fn key(exercise: &Exercise, sandboxed: bool) -> Option<String> {
+    let mut input = format!(
+        "rustlings cache v{CACHE_VERSION}\n{}\n{:?}\n{}\n{:?}\n{sandboxed}\n",
+        exercise.name,
+        exercise.mode,
+        rustc_version()?,
+        exercise::build_flags(exercise.mode),
+    )
+    .into_bytes();
+    for file in exercise.source_files() {
+        let contents = fs::read(&file).ok()?;
+        input.extend_from_slice(format!("{}\n", file.display()).as_bytes());
+        input.extend_from_slice(sha256_hex(contents).as_bytes());
+        input.push(b'\n');
+    }
+    Some(sha256_hex(input))
+}
+
+// The rustc version only has to be asked for once per run
+fn rustc_version() -> Option<&'static str> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+    VERSION.get_or_init(exercise::rustc_version).as_deref()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    fn exercise(path: &str, mode: Mode) -> Exercise {
+        Exercise::for_test("example", path, mode)
+    }
+
+    #[test]
+    fn test_key_depends_on_source_and_mode() {
+        let pending = exercise("tests/fixture/state/pending_exercise.rs", Mode::Compile);
+        let finished = exercise("tests/fixture/state/finished_exercise.rs", Mode::Compile);
+        let clippy = exercise("tests/fixture/state/pending_exercise.rs", Mode::Clippy);
+        assert_eq!(key(&pending, true), key(&pending, true));
+        assert_ne!(key(&pending, true), key(&finished, true));
+        assert_ne!(key(&pending, true), key(&clippy, true));
+    }
+
+    #[test]
+    fn test_key_depends_on_sandbox() {
+        let pending = exercise("tests/fixture/state/pending_exercise.rs", Mode::Compile);
+        assert_ne!(key(&pending, true), key(&pending, false));
+    }
+
+    #[test]
+    fn test_no_key_without_source() {
+        let missing = exercise("tests/fixture/state/missing_exercise.rs", Mode::Compile);
+        assert_eq!(key(&missing, true), None);
+    }
+
+    #[test]
+    fn test_timeouts_are_not_cached() {
+        let output = |timed_out| ExerciseOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            crashed: false,
+            timed_out,
+            diagnostics: Vec::new(),
+        };
+        assert!(Outcome::RunError(output(false)).is_reproducible());
+        assert!(!Outcome::RunError(output(true)).is_reproducible());
+        assert!(Outcome::Success(output(false)).is_reproducible());
+        assert!(!Outcome::Cancelled.is_reproducible());
+    }
+}
@@ -0,0 +1,227 @@
+// Checks that info.toml is consistent with the exercises next to it.
+//
+// Meant for maintainers: it reports every problem it finds instead of
+// stopping at the first one, each with the line of info.toml it's about.
+
+use crate::exercise::{Exercise, ExerciseList, Mode};
+use crate::fingerprint::test_fingerprint;
+use crate::originals;
+use crate::pool;
+use glob::glob;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const INFO_FILE: &str = "info.toml";
+const EXERCISES_DIR: &str = "exercises";
+const CATEGORIES_FILE: &str = "exercises/README.md";
+
+pub fn check_info(jobs: usize) -> Result<(), ()> {
+    let text = fs::read_to_string(INFO_FILE).map_err(|e| {
+        warn!("Failed to read {}: {}", INFO_FILE, e);
+    })?;
+    let exercises = toml::from_str::<ExerciseList>(&text)
+        .map_err(|e| {
+            warn!("{} can't be parsed: {}", INFO_FILE, e);
+        })?
+        .exercises;
+    let lines = entry_lines(&text);
+    let line = |index: usize| lines.get(index).copied().unwrap_or_default();
+
+    let mut problems = 0;
+    let mut report = |message: String| {
+        warn!("{}", message);
+        problems += 1;
+    };
+
+    let mut names = HashMap::new();
+    for (index, exercise) in exercises.iter().enumerate() {
+        if let Some(&first) = names.get(exercise.name.as_str()) {
+            report(format!(
+                "{INFO_FILE}:{}: the name {} is already used on line {}",
+                line(index),
+                exercise.name,
+                line(first)
+            ));
+        } else {
+            names.insert(exercise.name.as_str(), index);
+        }
+        if !exercise.path.exists() {
+            report(format!(
+                "{INFO_FILE}:{}: {} doesn't exist",
+                line(index),
+                exercise.path.display()
+            ));
+        }
+        // The snapshot has to follow every change to the exercises, or
+        // resetting brings back an outdated version
+        if let (Ok(original), Ok(current)) = (
+            originals::original(exercise),
+            fs::read_to_string(&exercise.path),
+        ) {
+            if original != current {
+                report(format!(
+                    "{INFO_FILE}:{}: {} differs from its original in {}, update the snapshot",
+                    line(index),
+                    exercise.path.display(),
+                    originals::SNAPSHOT_DIR
+                ));
+            }
+        }
+    }
+
+    for (index, exercise) in exercises.iter().enumerate() {
+        let has_tests = matches!(exercise.mode, Mode::Test | Mode::BuildScript);
+        if !has_tests || exercise.editable_tests {
+            continue;
+        }
+        // Taken from the exercise as rustlings was built with it, so solving
+        // the exercise in this checkout doesn't change the fingerprint
+        let Ok(source) =
+            originals::original(exercise).or_else(|_| fs::read_to_string(&exercise.path))
+        else {
+            continue;
+        };
+        let fingerprint = test_fingerprint(&source);
+        match &exercise.test_fingerprint {
+            Some(expected) if *expected == fingerprint => {}
+            Some(_) => report(format!(
+                "{INFO_FILE}:{}: the tests of {} don't match its test_fingerprint, it should be \"{fingerprint}\"",
+                line(index),
+                exercise.name
+            )),
+            None => report(format!(
+                "{INFO_FILE}:{}: {} has no test_fingerprint, add `test_fingerprint = \"{fingerprint}\"` or `editable_tests = true`",
+                line(index),
+                exercise.name
+            )),
+        }
+    }
+
+    let listed: BTreeSet<PathBuf> = exercises
+        .iter()
+        .flat_map(|e| e.source_files())
+        .map(|path| normalize(&path))
+        .collect();
+    for file in exercise_files() {
+        if !listed.contains(&file) {
+            report(format!("{} isn't listed in {INFO_FILE}", file.display()));
+        }
+    }
+
+    let known_categories = categories();
+    let mut reported_categories = BTreeSet::new();
+    for (index, exercise) in exercises.iter().enumerate() {
+        let Some(category) = exercise.category() else {
+            continue;
+        };
+        let known = known_categories
+            .as_ref()
+            .is_some_and(|known| known.contains(&category));
+        if !known && reported_categories.insert(category.clone()) {
+            report(format!(
+                "{INFO_FILE}:{}: the category {category} of {} is missing from {CATEGORIES_FILE}",
+                line(index),
+                exercise.name
+            ));
+        }
+    }
+
+    // Exercises without `I AM NOT DONE` have to fail, otherwise there's nothing to do
+    let unmarked: Vec<(usize, &Exercise)> = exercises
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.path.exists() && e.looks_done())
+        .collect();
+    pool::run_ordered(
+        &unmarked,
+        jobs,
+        |(_, e)| passes(e),
+        |i, passes| {
+            let (index, exercise) = unmarked[i];
+            if passes {
+                report(format!(
+                    "{INFO_FILE}:{}: {} already passes and has no `I AM NOT DONE` comment",
+                    line(index),
+                    exercise.name
+                ));
+            }
+        },
+    );
+
+    if problems == 0 {
+        success!(
+            "{} is consistent with the {} exercises",
+            INFO_FILE,
+            exercises.len()
+        );
+        Ok(())
+    } else {
+        warn!("Found {} problems in {}", problems, INFO_FILE);
+        Err(())
+    }
+}
+
+// Whether the exercise compiles and runs successfully as it is
+fn passes(exercise: &Exercise) -> bool {
+    match exercise.compile() {
+        Ok(compilation) => compilation.run().is_ok(),
+        Err(_) => false,
+    }
+}
+
+// The line numbers of the `[[exercises]]` headers, in the order of the exercises
+fn entry_lines(text: &str) -> Vec<usize> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| line.trim() == "[[exercises]]")
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+// All Rust files in the exercises directory, apart from build artifacts
+fn exercise_files() -> Vec<PathBuf> {
+    let pattern = format!("{EXERCISES_DIR}/**/*.rs");
+    glob(&pattern)
+        .map(|paths| {
+            paths
+                .filter_map(Result::ok)
+                .filter(|path| !path.components().any(|c| c.as_os_str() == "target"))
+                .map(|path| normalize(&path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// The categories in the table of exercises/README.md,
+// or None if there is no such file
+fn categories() -> Option<BTreeSet<String>> {
+    let readme = fs::read_to_string(CATEGORIES_FILE).ok()?;
+    Some(
+        readme
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix('|'))
+            .filter_map(|row| row.split('|').next())
+            .map(|cell| cell.trim().to_string())
+            .filter(|cell| cell != "Exercise" && !cell.starts_with('-'))
+            .collect(),
+    )
+}
+
+// Drop any `./` so that paths from info.toml and from the file system compare equal
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_entry_lines() {
+        let text = "# comment\n[[exercises]]\nname = \"a\"\n\n  [[exercises]]\nname = \"b\"\n";
+        assert_eq!(entry_lines(text), vec![2, 5]);
+    }
+}
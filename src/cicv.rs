@@ -0,0 +1,681 @@
+use crate::diagnostics::Diagnostic;
+use crate::digest::{hmac_hex, hmac_verify, sha256_hex};
+use crate::exercise::{failed_tests, rustc_version, Exercise, ExerciseOutput, Mode};
+use crate::pool;
+use crate::reporter::reporter;
+use crate::sandbox;
+use crate::state::ProgressState;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+
+// Where the results are written to unless `--output` says otherwise
+pub const DEFAULT_RESULT_PATH: &str = ".github/result/check_result.json";
+const USER_NAME_ENV: &str = "RUSTLINGS_USER_NAME";
+// The results are signed if this environment variable holds a key
+pub const REPORT_KEY_ENV: &str = "RUSTLINGS_REPORT_KEY";
+// Bump this whenever the layout of check_result.json changes
+const SCHEMA_VERSION: u32 = 9;
+// The maximum number of bytes of compiler or test output kept per exercise
+const MAX_DIAGNOSTICS_LEN: usize = 4096;
+const TEST_RESULT_REGEX: &str =
+    r"(?m)^test result: \w+\. (\d+) passed; (\d+) failed; (\d+) ignored;";
+
+#[derive(Deserialize, Serialize)]
+pub struct ExerciseCheckList {
+    // Files written before the schema was versioned are version 1
+    #[serde(default = "first_schema_version")]
+    pub schema_version: u32,
+    pub exercises: Vec<ExerciseResult>,
+    pub user_name: Option<String>,
+    pub statistics: ExerciseStatistics,
+    // The output of `rustc --version` on the grading machine
+    #[serde(default)]
+    pub rustc_version: Option<String>,
+    // The SHA-256 hashes of all graded source files, by path
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    // The HMAC-SHA256 of the report as it was written without this field,
+    // if a key was given. It's always the last field, see `sign`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ExerciseResult {
+    pub name: String,
+    // Whether the exercise passed, including its hidden tests if it has any
+    pub result: bool,
+    // Why the exercise failed, if it did. This and the fields up to
+    // `diagnostics` are about the exercise as the student sees it.
+    #[serde(default)]
+    pub failure: Option<FailureKind>,
+    // How long compiling and running the exercise took
+    #[serde(default)]
+    pub duration_ms: u64,
+    // The names of the tests that failed, as reported by the test harness
+    #[serde(default)]
+    pub failed_tests: Vec<String>,
+    // How many tests passed, failed and were ignored, if the tests ran
+    #[serde(default)]
+    pub tests: Option<TestCounts>,
+    // The (truncated) compiler or test output of a failed exercise
+    #[serde(default)]
+    pub diagnostics: Option<String>,
+    // The errors and warnings of the compiler, if the exercise didn't compile
+    #[serde(default)]
+    pub compiler_messages: Vec<Diagnostic>,
+    // How many of the exercise's `hint_levels` hints the student revealed,
+    // as recorded in .rustlings-hints.json. The student can edit that file
+    // like any other, so this is what they say rather than a proven count.
+    #[serde(default)]
+    pub hints_revealed: usize,
+    #[serde(default)]
+    pub hint_levels: usize,
+    // The results of the hidden tests, if the exercise has any and
+    // cicvverify was given the directory they are in
+    #[serde(default)]
+    pub hidden_tests: Option<TestSuiteResult>,
+    // The results of running all tests including the `#[ignore = "bonus"]`
+    // ones, if the exercise has any. They don't count towards `result` or `score`.
+    #[serde(default)]
+    pub bonus: Option<TestSuiteResult>,
+    // The share of `max_score` the exercise earned, see `score`
+    #[serde(default)]
+    pub score: f64,
+    // The weight of the exercise in info.toml
+    #[serde(default)]
+    pub max_score: u32,
+}
+
+// The results of running the tests of an exercise in addition to its own.
+// There are no diagnostics here, as the output would give hidden tests away.
+#[derive(Deserialize, Serialize)]
+pub struct TestSuiteResult {
+    pub result: bool,
+    // Why the tests failed, if they did
+    pub failure: Option<FailureKind>,
+    // The names of the tests that failed
+    pub failed_tests: Vec<String>,
+    // How many tests passed, failed and were ignored, if they ran
+    #[serde(default)]
+    pub tests: Option<TestCounts>,
+}
+
+// The counts from the summary line of a libtest harness
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Debug)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+}
+
+impl TestCounts {
+    fn add(self, other: TestCounts) -> TestCounts {
+        TestCounts {
+            passed: self.passed + other.passed,
+            failed: self.failed + other.failed,
+            ignored: self.ignored + other.ignored,
+        }
+    }
+}
+
+// The reason an exercise failed
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    // The exercise doesn't compile
+    CompileError,
+    // The exercise compiles, but Clippy reports lints
+    ClippyLint,
+    // At least one test of the exercise failed
+    TestFailure,
+    // The exercise exited with an error, e.g. because it panicked
+    RuntimeError,
+    // The exercise was killed by a signal, e.g. on a stack overflow
+    Crash,
+    // The exercise ran for too long and was stopped
+    Timeout,
+    // The tests the exercise came with were changed, so it wasn't graded
+    TestsModified,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ExerciseStatistics {
+    pub total_exercations: usize,
+    pub total_succeeds: usize,
+    pub total_failures: usize,
+    pub total_time: u32,
+    // The sum of the scores of all exercises, and what it could have been
+    #[serde(default)]
+    pub score: f64,
+    #[serde(default)]
+    pub max_score: u32,
+}
+
+// Grade all exercises, running at most `jobs` of them at the same time,
+// and write the results as JSON to `output`.
+// The results are listed (and printed) in the order of info.toml.
+//
+// Exercises never get to see the hidden tests: the directory they are in is
+// hidden in the sandbox. Without a sandbox, the output of the exercises is
+// left out of the report instead, as they could print the hidden tests.
+pub fn cicvverify(
+    exercises: &[Exercise],
+    jobs: usize,
+    output: &Path,
+    user_name: Option<String>,
+    hidden_tests: Option<&Path>,
+) -> io::Result<()> {
+    let now_start = Instant::now();
+    let alls = exercises.len();
+    let mut exercise_check_list = ExerciseCheckList {
+        schema_version: SCHEMA_VERSION,
+        exercises: Vec::with_capacity(alls),
+        user_name,
+        statistics: ExerciseStatistics {
+            total_exercations: alls,
+            total_succeeds: 0,
+            total_failures: 0,
+            total_time: 0,
+            score: 0.0,
+            max_score: exercises.iter().map(Exercise::weight).sum(),
+        },
+        rustc_version: rustc_version(),
+        files: hash_files(exercises),
+        signature: None,
+    };
+
+    if let Some(dir) = hidden_tests {
+        sandbox::hide(dir);
+    }
+    let keep_output = hidden_tests.is_none() || sandbox::enabled();
+
+    let state = ProgressState::load(false);
+    let grade_all = |exercise: &Exercise| {
+        let mut result = grade(exercise);
+        if !keep_output {
+            result.diagnostics = None;
+            result.compiler_messages.clear();
+        }
+        if let Some(path) = hidden_tests_path(exercise, hidden_tests) {
+            let hidden = grade_hidden_tests(exercise, &path);
+            result.result &= hidden.result;
+            result.hidden_tests = Some(hidden);
+        }
+        result.score = score(exercise.weight(), &result);
+        result
+    };
+    pool::run_ordered(exercises, jobs, grade_all, |index, mut result| {
+        result.hints_revealed = state.hints_revealed(&exercises[index]);
+        let statistics = &mut exercise_check_list.statistics;
+        statistics.score += result.score;
+        if result.result {
+            statistics.total_succeeds += 1;
+            reporter().info(&format!("{}执行成功", result.name));
+        } else {
+            statistics.total_failures += 1;
+            reporter().info(&format!("{}执行失败", result.name));
+        }
+        reporter().info(&format!("总的题目数: {}", alls));
+        reporter().info(&format!(
+            "当前做正确的题目数: {}",
+            statistics.total_succeeds
+        ));
+        reporter().info(&format!(
+            "当前得分: {:.2} / {}",
+            statistics.score, statistics.max_score
+        ));
+        reporter().info(&format!(
+            "当前修改试卷耗时: {} s",
+            result.duration_ms / 1000
+        ));
+        exercise_check_list.exercises.push(result);
+    });
+
+    let total_time = now_start.elapsed().as_secs();
+    reporter().info(&format!("===============================试卷批改完成,总耗时: {} s; ==================================", total_time));
+    exercise_check_list.statistics.total_time = total_time as u32;
+    let mut serialized = serde_json::to_string_pretty(&exercise_check_list).unwrap();
+    if let Ok(key) = env::var(REPORT_KEY_ENV) {
+        serialized = sign(&serialized, key);
+    }
+    if let Some(dir) = output.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(output, serialized)
+}
+
+// Check a report written by `cicvverify`: it has to be signed with the key in
+// RUSTLINGS_REPORT_KEY, and the graded files in the current checkout have to be
+// the ones that were graded. Every problem that is found gets reported.
+pub fn verify_report(path: &Path) -> Result<(), ()> {
+    let Ok(key) = env::var(REPORT_KEY_ENV) else {
+        warn!(
+            "{} has to be set to the key the report was signed with",
+            REPORT_KEY_ENV
+        );
+        return Err(());
+    };
+    let text = fs::read_to_string(path).map_err(|e| {
+        warn!("Failed to read the report: {}", e);
+    })?;
+    let invalid = |e: serde_json::Error| {
+        warn!("The report is not a valid check_result.json: {}", e);
+    };
+    let report: ExerciseCheckList = serde_json::from_str(&text).map_err(invalid)?;
+
+    let signed = match &report.signature {
+        Some(_) if signature_matches(&text, &key) => {
+            success!("The signature of {} is valid", path.display());
+            true
+        }
+        Some(_) => {
+            warn!("The signature of {} doesn't match, the report was modified or signed with another key", path.display());
+            false
+        }
+        None => {
+            warn!("{} is not signed", path.display());
+            false
+        }
+    };
+
+    let mut unchanged = true;
+    for (file, expected) in &report.files {
+        match fs::read(file) {
+            Ok(contents) if sha256_hex(&contents) == *expected => {}
+            Ok(_) => {
+                warn!("{} was changed after it was graded", file);
+                unchanged = false;
+            }
+            Err(_) => {
+                warn!("{} doesn't exist in this checkout", file);
+                unchanged = false;
+            }
+        }
+    }
+    if report.files.is_empty() {
+        warn!(
+            "{} doesn't contain the hashes of the graded files",
+            path.display()
+        );
+        unchanged = false;
+    } else if unchanged {
+        success!(
+            "All {} graded files match this checkout",
+            report.files.len()
+        );
+    }
+    if let Some(version) = &report.rustc_version {
+        reporter().info(&format!("The exercises were graded with {version}"));
+    }
+
+    if signed && unchanged {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+// Hash every file that went into grading the exercises
+fn hash_files(exercises: &[Exercise]) -> BTreeMap<String, String> {
+    exercises
+        .iter()
+        .flat_map(|exercise| exercise.source_files())
+        .filter_map(|path| {
+            let contents = fs::read(&path).ok()?;
+            Some((path.display().to_string(), sha256_hex(contents)))
+        })
+        .collect()
+}
+
+// Add the signature to the pretty-printed report, as its last field.
+// It's computed over the exact text of the report, which `signature_matches`
+// gets back by taking the field off again. Parsing the report and writing it
+// out once more wouldn't do, as the scores needn't come out the same.
+fn sign(report: &str, key: impl AsRef<[u8]>) -> String {
+    let signature = hmac_hex(report, key);
+    let fields = report.strip_suffix("\n}").unwrap_or(report);
+    format!("{fields},\n  \"signature\": \"{signature}\"\n}}")
+}
+
+// Whether the report was signed with the key by `sign`
+fn signature_matches(report: &str, key: impl AsRef<[u8]>) -> bool {
+    let Some((fields, signature)) = report.rsplit_once(",\n  \"signature\": \"") else {
+        return false;
+    };
+    let Some(signature) = signature.strip_suffix("\"\n}") else {
+        return false;
+    };
+    hmac_verify(format!("{fields}\n}}"), key, signature)
+}
+
+// Determine whose exercises are being graded.
+// An explicitly given name wins over the RUSTLINGS_USER_NAME environment
+// variable, which in turn wins over `git config user.name`.
+pub fn user_name(name: Option<String>) -> Option<String> {
+    name.or_else(|| env::var(USER_NAME_ENV).ok())
+        .or_else(|| {
+            let output = Command::new("git")
+                .args(["config", "user.name"])
+                .output()
+                .ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .filter(|name| !name.trim().is_empty())
+}
+
+// Compile and run a single exercise without printing anything,
+// so that several exercises can be graded at the same time
+fn grade(exercise: &Exercise) -> ExerciseResult {
+    let now_start = Instant::now();
+    let mut bonus = None;
+    let (failure, output) = if exercise.tests_changed() {
+        let output = ExerciseOutput {
+            stdout: String::from("The tests of the exercise were changed"),
+            ..ExerciseOutput::default()
+        };
+        (Some(FailureKind::TestsModified), output)
+    } else {
+        match exercise.compile() {
+            Ok(compilation) => {
+                if exercise.has_bonus_tests() {
+                    bonus = Some(test_suite_result(
+                        exercise,
+                        compilation.run_with_bonus_tests(),
+                    ));
+                }
+                match compilation.run() {
+                    Ok(output) => (None, output),
+                    Err(output) => (Some(run_failure_kind(exercise, &output)), output),
+                }
+            }
+            Err(output) => (Some(compile_failure_kind(exercise, &output)), output),
+        }
+    };
+    let duration_ms = now_start.elapsed().as_millis() as u64;
+    let tests = match exercise.mode {
+        Mode::Test | Mode::BuildScript => test_counts(&output.stdout),
+        Mode::Compile | Mode::Clippy => None,
+    };
+
+    match failure {
+        None => ExerciseResult {
+            name: exercise.name.clone(),
+            result: true,
+            failure: None,
+            duration_ms,
+            failed_tests: Vec::new(),
+            tests,
+            diagnostics: None,
+            compiler_messages: Vec::new(),
+            hints_revealed: 0,
+            hint_levels: exercise.hint.levels().len(),
+            hidden_tests: None,
+            bonus,
+            score: 0.0,
+            max_score: exercise.weight(),
+        },
+        Some(kind) => {
+            // Compilers only write to stderr, while test harnesses report on stdout
+            let text = match kind {
+                FailureKind::CompileError | FailureKind::ClippyLint => output.stderr,
+                _ => format!("{}{}", output.stdout, output.stderr),
+            };
+            ExerciseResult {
+                name: exercise.name.clone(),
+                result: false,
+                failure: Some(kind),
+                duration_ms,
+                failed_tests: failed_tests(&output.stdout),
+                tests,
+                diagnostics: Some(truncate(&console::strip_ansi_codes(&text))),
+                compiler_messages: output.diagnostics,
+                hints_revealed: 0,
+                hint_levels: exercise.hint.levels().len(),
+                hidden_tests: None,
+                bonus,
+                score: 0.0,
+                max_score: exercise.weight(),
+            }
+        }
+    }
+}
+
+// A passing exercise earns its whole weight. Otherwise a test exercise earns
+// the share of its tests (including the hidden ones) that passed, as long as
+// they all compiled and ran to the end.
+fn score(weight: u32, result: &ExerciseResult) -> f64 {
+    if result.result {
+        return weight as f64;
+    }
+    let Some(mut tests) = result.tests else {
+        return 0.0;
+    };
+    if let Some(hidden) = &result.hidden_tests {
+        match hidden.tests {
+            Some(hidden) => tests = tests.add(hidden),
+            None => return 0.0,
+        }
+    }
+    let graded = tests.passed + tests.failed;
+    if graded == 0 {
+        return 0.0;
+    }
+    let score = weight as f64 * tests.passed as f64 / graded as f64;
+    (score * 100.0).round() / 100.0
+}
+
+// The hidden tests of the exercise within the directory given to cicvverify.
+// Only test exercises have hidden tests.
+fn hidden_tests_path(exercise: &Exercise, dir: Option<&Path>) -> Option<PathBuf> {
+    match (exercise.mode, dir, &exercise.hidden_tests) {
+        (Mode::Test, Some(dir), Some(path)) => Some(dir.join(path)),
+        _ => None,
+    }
+}
+
+// Compile the exercise with its hidden tests and run only those
+fn grade_hidden_tests(exercise: &Exercise, hidden_tests: &Path) -> TestSuiteResult {
+    match exercise.compile_with_hidden_tests(hidden_tests) {
+        Ok(compilation) => test_suite_result(exercise, compilation.run()),
+        Err(_) => TestSuiteResult {
+            result: false,
+            failure: Some(FailureKind::CompileError),
+            failed_tests: Vec::new(),
+            tests: None,
+        },
+    }
+}
+
+fn test_suite_result(
+    exercise: &Exercise,
+    run: Result<ExerciseOutput, ExerciseOutput>,
+) -> TestSuiteResult {
+    let (failure, output) = match run {
+        Ok(output) => (None, output),
+        Err(output) => (Some(run_failure_kind(exercise, &output)), output),
+    };
+    TestSuiteResult {
+        result: failure.is_none(),
+        failure,
+        failed_tests: failed_tests(&output.stdout),
+        tests: test_counts(&output.stdout),
+    }
+}
+
+fn compile_failure_kind(exercise: &Exercise, output: &ExerciseOutput) -> FailureKind {
+    if output.timed_out {
+        return FailureKind::Timeout;
+    }
+    // Clippy only gets to lint code that compiles, so any rustc error wins
+    let mut errors = output
+        .diagnostics
+        .iter()
+        .filter(|d| d.is_error())
+        .peekable();
+    let is_lint = errors.peek().is_some()
+        && errors.all(|d| {
+            d.code
+                .as_deref()
+                .is_some_and(|code| code.starts_with("clippy::"))
+        });
+    match exercise.mode {
+        Mode::Clippy if is_lint => FailureKind::ClippyLint,
+        _ => FailureKind::CompileError,
+    }
+}
+
+fn run_failure_kind(exercise: &Exercise, output: &ExerciseOutput) -> FailureKind {
+    if output.timed_out {
+        return FailureKind::Timeout;
+    }
+    if output.crashed {
+        return FailureKind::Crash;
+    }
+    match exercise.mode {
+        Mode::Test | Mode::BuildScript => FailureKind::TestFailure,
+        Mode::Compile | Mode::Clippy => FailureKind::RuntimeError,
+    }
+}
+
+// The counts of a libtest harness, added up over all harnesses in the output
+// (cargo runs one for the unit tests and one for the doc tests),
+// or None if no harness ran to the end
+pub fn test_counts(stdout: &str) -> Option<TestCounts> {
+    let re = Regex::new(TEST_RESULT_REGEX).unwrap();
+    re.captures_iter(stdout)
+        .map(|captures| TestCounts {
+            passed: captures[1].parse().unwrap_or_default(),
+            failed: captures[2].parse().unwrap_or_default(),
+            ignored: captures[3].parse().unwrap_or_default(),
+        })
+        .reduce(TestCounts::add)
+}
+
+// Cut the text down to MAX_DIAGNOSTICS_LEN bytes, on a character boundary
+fn truncate(text: &str) -> String {
+    if text.len() <= MAX_DIAGNOSTICS_LEN {
+        return text.to_string();
+    }
+    let mut end = MAX_DIAGNOSTICS_LEN;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n... (truncated)", &text[..end])
+}
+
+fn first_schema_version() -> u32 {
+    1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_failed_tests() {
+        let stdout = "running 3 tests\n\
+            test tests::passes ... ok\n\
+            test tests::fails ... FAILED\n\
+            test other::also_fails ... FAILED\n\n\
+            failures:\n\n\
+            ---- tests::fails stdout ----\n";
+        assert_eq!(
+            failed_tests(stdout),
+            vec!["tests::fails", "other::also_fails"]
+        );
+    }
+
+    #[test]
+    fn test_test_counts() {
+        let stdout =
+            "test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out\n\
+            test result: ok. 1 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(
+            test_counts(stdout),
+            Some(TestCounts {
+                passed: 3,
+                failed: 1,
+                ignored: 1
+            })
+        );
+        assert_eq!(test_counts("error[E0308]: mismatched types"), None);
+    }
+
+    #[test]
+    fn test_score() {
+        let json = r#"{ "name": "tests1", "result": false, "tests": { "passed": 1, "failed": 2, "ignored": 1 } }"#;
+        let mut result: ExerciseResult = serde_json::from_str(json).unwrap();
+        assert_eq!(score(3, &result), 1.0);
+        assert_eq!(score(2, &result), 0.67);
+        result.hidden_tests = Some(TestSuiteResult {
+            result: false,
+            failure: Some(FailureKind::CompileError),
+            failed_tests: Vec::new(),
+            tests: None,
+        });
+        assert_eq!(score(3, &result), 0.0);
+        result.result = true;
+        assert_eq!(score(3, &result), 3.0);
+    }
+
+    #[test]
+    fn test_sign_fractional_scores() {
+        let json = r#"{
+            "exercises": [{ "name": "tests1", "result": false, "score": 0.67, "max_score": 1 }],
+            "user_name": null,
+            "statistics": {
+                "total_exercations": 1,
+                "total_succeeds": 0,
+                "total_failures": 1,
+                "total_time": 0
+            }
+        }"#;
+        let mut list: ExerciseCheckList = serde_json::from_str(json).unwrap();
+        list.statistics.score = 2.0 / 3.0;
+        let report = sign(&serde_json::to_string_pretty(&list).unwrap(), "key");
+
+        assert!(signature_matches(&report, "key"));
+        assert!(!signature_matches(&report, "other key"));
+        assert!(!signature_matches(&report.replace("0.67", "0.68"), "key"));
+        let list: ExerciseCheckList = serde_json::from_str(&report).unwrap();
+        assert_eq!(list.statistics.score, 2.0 / 3.0);
+        assert!(list.signature.is_some());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short"), "short");
+        let long = "é".repeat(MAX_DIAGNOSTICS_LEN);
+        let truncated = truncate(&long);
+        assert!(truncated.len() <= MAX_DIAGNOSTICS_LEN + "\n... (truncated)".len());
+        assert!(truncated.ends_with("(truncated)"));
+    }
+
+    #[test]
+    fn test_read_unversioned_results() {
+        let json = r#"{
+            "exercises": [{ "name": "intro2", "result": true }],
+            "user_name": null,
+            "statistics": {
+                "total_exercations": 1,
+                "total_succeeds": 1,
+                "total_failures": 0,
+                "total_time": 0
+            }
+        }"#;
+        let list: ExerciseCheckList = serde_json::from_str(json).unwrap();
+        assert_eq!(list.schema_version, 1);
+        assert_eq!(list.exercises[0].failure, None);
+    }
+}
@@ -0,0 +1,255 @@
+// Compiler diagnostics, collected with `--error-format=json`.
+//
+// Instead of passing on whatever rustc or cargo print, their diagnostics are
+// read as data and rendered here: duplicates are dropped, only the first few
+// errors are shown, and every error code comes with the summary of its
+// explanation, which rustc includes in the JSON. The JSON report keeps the
+// diagnostics as they are, for editors and other tools.
+
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+// How many errors are shown, the first ones are usually the ones to fix first
+pub const MAX_ERRORS: usize = 3;
+
+// A single error, warning or lint of the compiler
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct Diagnostic {
+    // "error", "warning", "note", "help" or "error: internal compiler error"
+    pub level: String,
+    pub message: String,
+    // The error code or the name of the lint, e.g. "E0308" or "clippy::float_cmp"
+    pub code: Option<String>,
+    // The first line of what `rustc --explain` says about the code
+    pub explanation: Option<String>,
+    // Where in the source the diagnostic points to
+    pub spans: Vec<Span>,
+    // The diagnostic as the compiler prints it, including ANSI colors
+    pub rendered: String,
+}
+
+// A part of the source a diagnostic is about. Lines and columns start at 1.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct Span {
+    pub file_name: String,
+    pub line_start: usize,
+    pub column_start: usize,
+    pub line_end: usize,
+    pub column_end: usize,
+    pub label: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.level.starts_with("error")
+    }
+}
+
+// A diagnostic as rustc writes it. Cargo wraps the same in a `message` field.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Line {
+    Cargo { message: RawDiagnostic },
+    Rustc(RawDiagnostic),
+}
+
+#[derive(Deserialize)]
+struct RawDiagnostic {
+    message: String,
+    code: Option<RawCode>,
+    level: String,
+    spans: Vec<RawSpan>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawCode {
+    code: String,
+    explanation: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawSpan {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    line_end: usize,
+    column_end: usize,
+    is_primary: bool,
+    label: Option<String>,
+}
+
+// The diagnostics in the output of rustc with `--error-format=json` or of
+// cargo with `--message-format=json`. Lines that aren't diagnostics are
+// skipped, as are the closing notes like "aborting due to 2 previous errors".
+pub fn parse(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str::<Line>(line).ok())
+        .map(|line| match line {
+            Line::Cargo { message } | Line::Rustc(message) => message,
+        })
+        .filter(|raw| raw.level != "failure-note" && !raw.message.starts_with("aborting due to"))
+        .map(|raw| Diagnostic {
+            level: raw.level,
+            message: raw.message,
+            explanation: raw
+                .code
+                .as_ref()
+                .and_then(|code| code.explanation.as_deref())
+                .and_then(|text| text.lines().find(|line| !line.trim().is_empty()))
+                .map(|line| line.trim().to_string()),
+            code: raw.code.map(|code| code.code),
+            spans: raw
+                .spans
+                .into_iter()
+                .filter(|span| span.is_primary)
+                .map(|span| Span {
+                    file_name: span.file_name,
+                    line_start: span.line_start,
+                    column_start: span.column_start,
+                    line_end: span.line_end,
+                    column_end: span.column_end,
+                    label: span.label,
+                })
+                .collect(),
+            rendered: raw.rendered.unwrap_or_default(),
+        })
+        .collect()
+}
+
+// Render the diagnostics for the terminal. If there are errors, only the
+// first `MAX_ERRORS` of them are shown, warnings would only distract from
+// what keeps the exercise from compiling.
+pub fn render(diagnostics: &[Diagnostic]) -> String {
+    let mut seen = BTreeSet::new();
+    let unique: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|d| seen.insert(d.rendered.as_str()))
+        .collect();
+    let has_errors = unique.iter().any(|d| d.is_error());
+    let shown: Vec<&Diagnostic> = unique
+        .iter()
+        .copied()
+        .filter(|d| d.is_error() || !has_errors)
+        .collect();
+
+    let mut text = String::new();
+    for diagnostic in shown.iter().take(MAX_ERRORS) {
+        text += diagnostic.rendered.trim_end();
+        text += "\n\n";
+    }
+    let hidden = shown.len().saturating_sub(MAX_ERRORS);
+    if hidden > 0 {
+        let s = if hidden == 1 { "" } else { "s" };
+        let kind = if has_errors { "error" } else { "warning" };
+        text += &format!("... and {hidden} more {kind}{s}, fix the ones above first.\n\n");
+    }
+    let mut explained = BTreeSet::new();
+    for diagnostic in shown.iter().take(MAX_ERRORS) {
+        if let (Some(code), Some(explanation)) = (&diagnostic.code, &diagnostic.explanation) {
+            if explained.insert(code) {
+                text += &format!(
+                    "{} {explanation} Run `rustc --explain {code}` to learn more.\n",
+                    style(format!("{code}:")).bold()
+                );
+            }
+        }
+    }
+    text.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(message: &str, code: Option<&str>) -> Diagnostic {
+        Diagnostic {
+            level: String::from("error"),
+            message: message.to_string(),
+            code: code.map(String::from),
+            explanation: code.map(|code| format!("What {code} means.")),
+            spans: Vec::new(),
+            rendered: format!("error: {message}"),
+        }
+    }
+
+    fn warning(message: &str) -> Diagnostic {
+        Diagnostic {
+            level: String::from("warning"),
+            rendered: format!("warning: {message}"),
+            ..error(message, None)
+        }
+    }
+
+    #[test]
+    fn test_parse_rustc() {
+        let output = r#"{"$message_type":"diagnostic","message":"mismatched types","code":{"code":"E0308","explanation":"Expected type did not match the received type.\n\nErroneous code examples:\n"},"level":"error","spans":[{"file_name":"exercises/a.rs","byte_start":29,"byte_end":33,"line_start":2,"line_end":2,"column_start":18,"column_end":22,"is_primary":true,"text":[],"label":"expected `i32`, found `&str`","suggested_replacement":null,"suggestion_applicability":null,"expansion":null},{"file_name":"exercises/a.rs","byte_start":23,"byte_end":26,"line_start":2,"line_end":2,"column_start":12,"column_end":15,"is_primary":false,"text":[],"label":"expected due to this","suggested_replacement":null,"suggestion_applicability":null,"expansion":null}],"children":[],"rendered":"error[E0308]: mismatched types\n"}
+{"$message_type":"diagnostic","message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[],"rendered":"error: aborting due to 1 previous error\n\n"}
+{"$message_type":"diagnostic","message":"For more information about this error, try `rustc --explain E0308`.","code":null,"level":"failure-note","spans":[],"children":[],"rendered":"For more information about this error, try `rustc --explain E0308`.\n"}
+"#;
+        assert_eq!(
+            parse(output),
+            [Diagnostic {
+                level: String::from("error"),
+                message: String::from("mismatched types"),
+                code: Some(String::from("E0308")),
+                explanation: Some(String::from(
+                    "Expected type did not match the received type."
+                )),
+                spans: vec![Span {
+                    file_name: String::from("exercises/a.rs"),
+                    line_start: 2,
+                    column_start: 18,
+                    line_end: 2,
+                    column_end: 22,
+                    label: Some(String::from("expected `i32`, found `&str`")),
+                }],
+                rendered: String::from("error[E0308]: mismatched types\n"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_cargo() {
+        let output = r#"{"reason":"compiler-artifact","package_id":"a 0.0.1","target":{"name":"a"},"fresh":false}
+{"reason":"compiler-message","package_id":"a 0.0.1","message":{"message":"strict comparison of `f32` or `f64`","code":{"code":"clippy::float_cmp","explanation":null},"level":"error","spans":[],"children":[],"rendered":"error: strict comparison\n"}}
+   Compiling a v0.0.1
+{"reason":"build-finished","success":false}
+"#;
+        let diagnostics = parse(output);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("clippy::float_cmp"));
+        assert_eq!(diagnostics[0].explanation, None);
+    }
+
+    #[test]
+    fn test_render_limits_and_dedupes_errors() {
+        let diagnostics = [
+            warning("unused variable"),
+            error("a", Some("E0308")),
+            error("a", Some("E0308")),
+            error("b", Some("E0308")),
+            error("c", None),
+            error("d", Some("E0425")),
+            error("e", None),
+        ];
+        let rendered = console::strip_ansi_codes(&render(&diagnostics)).to_string();
+        assert_eq!(
+            rendered,
+            "error: a\n\nerror: b\n\nerror: c\n\n\
+             ... and 2 more errors, fix the ones above first.\n\n\
+             E0308: What E0308 means. Run `rustc --explain E0308` to learn more."
+        );
+    }
+
+    #[test]
+    fn test_render_warnings_without_errors() {
+        assert_eq!(
+            render(&[warning("unused variable")]),
+            "warning: unused variable"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use hmac_sha256::{Hash, HMAC};
+use std::fmt::Write;
+
+// The SHA-256 hash of the given bytes, as lowercase hex
+pub fn sha256_hex(bytes: impl AsRef<[u8]>) -> String {
+    to_hex(&Hash::hash(bytes.as_ref()))
+}
+
+// The HMAC-SHA256 of the message with the given key, as lowercase hex
+pub fn hmac_hex(message: impl AsRef<[u8]>, key: impl AsRef<[u8]>) -> String {
+    to_hex(&HMAC::mac(message, key))
+}
+
+// Check a hex encoded HMAC-SHA256 in constant time
+pub fn hmac_verify(message: impl AsRef<[u8]>, key: impl AsRef<[u8]>, expected: &str) -> bool {
+    match from_hex(expected) {
+        Some(expected) => HMAC::verify(message, key, &expected),
+        None => false,
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+fn from_hex(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_hmac_roundtrip() {
+        let signature = hmac_hex("report", "key");
+        assert!(hmac_verify("report", "key", &signature));
+        assert!(!hmac_verify("report!", "key", &signature));
+        assert!(!hmac_verify("report", "other key", &signature));
+        assert!(!hmac_verify("report", "key", "not hex"));
+    }
+}
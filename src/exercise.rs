@@ -1,32 +1,83 @@
+use crate::diagnostics::{self, Diagnostic};
+use crate::fingerprint::test_fingerprint;
+use crate::limits::{self, output_with_limits, CancelToken, LimitedOutput, Limits};
+use crate::reporter;
+use crate::sandbox;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fmt::{self, Display, Formatter};
-use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
-use std::process::{self, Command};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::{self, Command, Output};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
+// Diagnostics are collected as JSON and rendered by `diagnostics::render`
+const RUSTC_JSON_ARGS: &[&str] = &["--error-format=json", "--json=diagnostic-rendered-ansi"];
+const CARGO_JSON_ARGS: &[&str] = &["--message-format=json-diagnostic-rendered-ansi"];
 const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
-const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
+const CLIPPY_ARGS: &[&str] = &["-D", "warnings", "-D", "clippy::float_cmp"];
+const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*(I\s+AM\s+NOT\s+DONE)";
+const FAILED_TEST_REGEX: &str = r"(?m)^test (\S+) \.\.\. FAILED\s*$";
+const BONUS_TEST_REGEX: &str = r#"#\[ignore\s*=\s*"bonus"\s*\]"#;
 const CONTEXT: usize = 2;
-const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/clippy/Cargo.toml";
-const BUILD_SCRIPT_CARGO_TOML_PATH: &str = "./exercises/tests/Cargo.toml";
-
-// Get a temporary file name that is hopefully unique
-#[inline]
-fn temp_file() -> String {
-    let thread_id: String = format!("{:?}", std::thread::current().id())
-        .chars()
-        .filter(|c| c.is_alphanumeric())
-        .collect();
-
-    format!("./temp_{}_{thread_id}", process::id())
+const BUILD_SCRIPT_FILE_NAME: &str = "build.rs";
+// The module hidden tests are wrapped in, see `compile_with_hidden_tests`
+const HIDDEN_TESTS_MODULE: &str = "rustlings_hidden_tests";
+
+// A temporary directory that a single exercise is compiled and run in.
+// Every compilation gets its own directory (and its own Cargo.toml, if needed),
+// so several exercises can be built at the same time without stepping on
+// each other. The directory is removed again once the handle is dropped.
+struct BuildDir {
+    path: PathBuf,
+}
+
+impl BuildDir {
+    fn new() -> io::Result<BuildDir> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = env::temp_dir().join(format!(
+            "rustlings_{}_{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        // A left-over directory can only come from a crashed run with the same pid
+        let _ignored = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path)?;
+        Ok(BuildDir { path })
+    }
+
+    // The path of the binary that rustc should produce
+    fn binary(&self) -> PathBuf {
+        self.path
+            .join(format!("exercise{}", env::consts::EXE_SUFFIX))
+    }
+
+    // The path of the generated Cargo.toml for cargo based modes
+    fn manifest(&self) -> PathBuf {
+        self.path.join("Cargo.toml")
+    }
+
+    // The target directory cargo should use, so that nothing is shared between builds
+    fn target_dir(&self) -> PathBuf {
+        self.path.join("target")
+    }
+}
+
+impl Drop for BuildDir {
+    fn drop(&mut self) {
+        let _ignored = fs::remove_dir_all(&self.path);
+    }
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Serialize, Copy, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
@@ -46,7 +97,7 @@ pub struct ExerciseList {
 
 // A representation of a rustlings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
@@ -55,7 +106,107 @@ pub struct Exercise {
     // The mode of the exercise (Test, Compile, or Clippy)
     pub mode: Mode,
     // The hint text associated with the exercise
-    pub hint: String,
+    pub hint: Hint,
+    // The number of seconds the exercise may run for, instead of the default
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    // Free-form tags to find the exercise by, e.g. with `list --tag`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // How hard the exercise is meant to be
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    // The sections of the Rust Book the exercise is about, e.g. "4.1"
+    #[serde(default)]
+    pub book_chapters: Vec<String>,
+    // Roughly how many minutes the exercise takes
+    #[serde(default)]
+    pub estimated_minutes: Option<u32>,
+    // The fingerprint of the tests the exercise comes with, see fingerprint.rs
+    #[serde(default)]
+    pub test_fingerprint: Option<String>,
+    // Whether the student is supposed to write or change the tests,
+    // in which case they don't have a fingerprint
+    #[serde(default)]
+    pub editable_tests: bool,
+    // A file of tests the student never gets to see, relative to the directory
+    // given to `cicvverify --hidden-tests`. Only used for test exercises.
+    #[serde(default)]
+    pub hidden_tests: Option<PathBuf>,
+    // How much the exercise counts towards the score of cicvverify, 1 by default
+    #[serde(default)]
+    pub weight: Option<u32>,
+    // Hints for the predictable ways the exercise fails, shown along with the
+    // failure. Keyed by rustc error code (e.g. "E0382"), Clippy lint (e.g.
+    // "clippy::float_cmp") or the name of a test, with or without its module.
+    #[serde(default)]
+    pub error_hints: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "unknown difficulty `{s}`, expected easy, medium or hard"
+            )),
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// The hints of an exercise. In info.toml this is either a single string,
+// or a list of hints that are revealed one after the other.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum Hint {
+    Single(String),
+    Levels(Vec<String>),
+}
+
+impl Hint {
+    // The hints in the order they are revealed in
+    pub fn levels(&self) -> &[String] {
+        match self {
+            Hint::Single(hint) => std::slice::from_ref(hint),
+            Hint::Levels(hints) => hints,
+        }
+    }
+}
+
+impl Default for Hint {
+    fn default() -> Self {
+        Hint::Single(String::new())
+    }
+}
+
+// All hints at once, separated by empty lines
+impl Display for Hint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.levels().join("\n\n"))
+    }
 }
 
 // An enum to track of the state of an Exercise.
@@ -77,159 +228,383 @@ pub struct ContextLine {
     pub number: usize,
     // Whether or not this is important
     pub important: bool,
+    // The column the `I AM NOT DONE` marker starts at, counting from 1,
+    // if it's on this line
+    pub marker_column: Option<usize>,
 }
 
 // The result of compiling an exercise
 pub struct CompiledExercise<'a> {
     exercise: &'a Exercise,
-    _handle: FileHandle,
+    build_dir: BuildDir,
+    // Only the tests whose names contain this are run
+    test_filter: Option<&'static str>,
+    // Cancels running the exercise along with compiling it
+    cancel: CancelToken,
 }
 
 impl<'a> CompiledExercise<'a> {
     // Run the compiled exercise
     pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        self.exercise.run()
+        self.exercise
+            .run(&self.build_dir, self.test_args(&[]), &self.cancel)
+    }
+
+    // Run the compiled tests, including the bonus tests that are usually ignored
+    pub fn run_with_bonus_tests(&self) -> Result<ExerciseOutput, ExerciseOutput> {
+        self.exercise.run(
+            &self.build_dir,
+            self.test_args(&["--include-ignored"]),
+            &self.cancel,
+        )
+    }
+
+    fn test_args<'s>(&'s self, args: &[&'s str]) -> Vec<&'s str> {
+        self.test_filter
+            .into_iter()
+            .chain(args.iter().copied())
+            .collect()
     }
 }
 
 // A representation of an already executed binary
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Default, Debug)]
 pub struct ExerciseOutput {
     // The textual contents of the standard output of the binary
     pub stdout: String,
     // The textual contents of the standard error of the binary
     pub stderr: String,
+    // Whether the binary was killed by a signal instead of exiting
+    pub crashed: bool,
+    // Whether the binary (or the compiler, for a failed compilation) was
+    // killed because it ran for too long
+    pub timed_out: bool,
+    // What the compiler reported, if this is the output of a failed compilation
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
-struct FileHandle;
+impl From<Output> for ExerciseOutput {
+    fn from(output: Output) -> Self {
+        #[cfg(unix)]
+        let crashed = {
+            use std::os::unix::process::ExitStatusExt;
+            output.status.signal().is_some()
+        };
+        #[cfg(not(unix))]
+        let crashed = false;
+
+        ExerciseOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            crashed,
+            timed_out: false,
+            diagnostics: Vec::new(),
+        }
+    }
+}
 
-impl Drop for FileHandle {
-    fn drop(&mut self) {
-        clean();
+impl From<LimitedOutput> for ExerciseOutput {
+    fn from(limited: LimitedOutput) -> Self {
+        let mut output = ExerciseOutput::from(limited.output);
+        // Being killed for running too long isn't a crash of the exercise itself
+        output.crashed &= !limited.timed_out;
+        output.timed_out = limited.timed_out;
+        output
     }
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        self.compile_cancellable(&CancelToken::default())
+    }
+
+    // Compile the exercise, unless the token is cancelled along the way.
+    // Running the result can be cancelled with the same token.
+    pub fn compile_cancellable(
+        &self,
+        cancel: &CancelToken,
+    ) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        let build_dir = BuildDir::new().expect("Failed to create a build directory!");
+        let source = env::current_dir()
+            .expect("Failed to get the current directory!")
+            .join(&self.path);
+
         let cmd = match self.mode {
-            Mode::Compile => Command::new("rustc")
-                .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
-                .args(RUSTC_EDITION_ARGS)
-                .output(),
-            Mode::Test => Command::new("rustc")
-                .args(&["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
-                .args(RUSTC_EDITION_ARGS)
-                .output(),
+            Mode::Compile => limits::compiler_output(
+                compiler("rustc", &build_dir)
+                    .arg(&source)
+                    .arg("-o")
+                    .arg(build_dir.binary())
+                    .args(RUSTC_JSON_ARGS)
+                    .args(RUSTC_EDITION_ARGS),
+                cancel,
+            ),
+            Mode::Test => limits::compiler_output(
+                compiler("rustc", &build_dir)
+                    .arg("--test")
+                    .arg(&source)
+                    .arg("-o")
+                    .arg(build_dir.binary())
+                    .args(RUSTC_JSON_ARGS)
+                    .args(RUSTC_EDITION_ARGS),
+                cancel,
+            ),
             Mode::Clippy => {
-                let cargo_toml = format!(
-                    r#"[package]
-name = "{}"
-version = "0.0.1"
-edition = "2021"
-[[bin]]
-name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
-                );
-                let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
-                    "Failed to write Clippy Cargo.toml file."
-                } else {
-                    "Failed to write 📎 Clippy 📎 Cargo.toml file."
-                };
-                fs::write(CLIPPY_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
+                self.write_manifest(&source, &build_dir);
                 // To support the ability to run the clippy exercises, build
                 // an executable, in addition to running clippy. With a
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
-                Command::new("rustc")
-                    .args(&[self.path.to_str().unwrap(), "-o", &temp_file()])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(RUSTC_EDITION_ARGS)
-                    .output()
-                    .expect("Failed to compile!");
-                // Due to an issue with Clippy, a cargo clean is required to catch all lints.
+                limits::compiler_output(
+                    compiler("rustc", &build_dir)
+                        .arg(&source)
+                        .arg("-o")
+                        .arg(build_dir.binary())
+                        .args(RUSTC_JSON_ARGS)
+                        .args(RUSTC_EDITION_ARGS),
+                    cancel,
+                )
+                .expect("Failed to compile!");
+                // Clippy used to need a `cargo clean` first to catch all lints.
                 // See https://github.com/rust-lang/rust-clippy/issues/2604
-                // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
-                // https://github.com/rust-lang/rust-clippy/issues/3837
-                Command::new("cargo")
-                    .args(&["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .output()
-                    .expect("Failed to run 'cargo clean'");
-                Command::new("cargo")
-                    .args(&["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
-                    .args(&["--", "-D", "warnings", "-D", "clippy::float_cmp"])
-                    .output()
-            },
+                // The target directory is fresh for every build, so that's no longer needed.
+                limits::compiler_output(
+                    compiler("cargo", &build_dir)
+                        .arg("clippy")
+                        .arg("--manifest-path")
+                        .arg(build_dir.manifest())
+                        .arg("--target-dir")
+                        .arg(build_dir.target_dir())
+                        .args(RUSTC_COLOR_ARGS)
+                        .args(CARGO_JSON_ARGS)
+                        .arg("--")
+                        .args(CLIPPY_ARGS),
+                    cancel,
+                )
+            }
             Mode::BuildScript => {
-                let cargo_toml = format!(
-                    r#"[package]
-name = "{}"
-version = "0.0.1"
-edition = "2021"
-[[bin]]
-name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
-                );
-                let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
-                    "Failed to write Clippy Cargo.toml file."
-                } else {
-                    "Failed to write 📎 Clippy 📎 Cargo.toml file."
-                };
-                fs::write(BUILD_SCRIPT_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
-
-                Command::new("cargo")
-                    .args(&["test", "--manifest-path", BUILD_SCRIPT_CARGO_TOML_PATH])
-                    .output()
+                self.write_manifest(&source, &build_dir);
+                limits::compiler_output(
+                    compiler("cargo", &build_dir)
+                        .args(["test", "--no-run", "--manifest-path"])
+                        .arg(build_dir.manifest())
+                        .arg("--target-dir")
+                        .arg(build_dir.target_dir())
+                        .args(RUSTC_COLOR_ARGS)
+                        .args(CARGO_JSON_ARGS),
+                    cancel,
+                )
             }
         }
         .expect("Failed to run 'compile' command.");
 
-        if cmd.status.success() {
+        if cmd.timed_out {
+            Err(cmd.into())
+        } else if cmd.output.status.success() {
             Ok(CompiledExercise {
                 exercise: self,
-                _handle: FileHandle,
+                build_dir,
+                test_filter: None,
+                cancel: cancel.clone(),
             })
         } else {
-            clean();
-            Err(ExerciseOutput {
-                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            Err(compile_error(cmd.output))
+        }
+    }
+
+    // Compile the exercise together with tests the student never gets to see.
+    // They are appended to the exercise as a module of their own, so they can
+    // use the exercise's items just like its own tests. The result is passed to
+    // the compiler on stdin and never written anywhere an exercise could read
+    // it. Running the result runs only the hidden tests.
+    pub fn compile_with_hidden_tests(
+        &self,
+        hidden_tests: &Path,
+    ) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        let read = |path: &Path| {
+            fs::read_to_string(path).map_err(|e| ExerciseOutput {
+                stderr: format!("Failed to read {}: {e}", path.display()),
+                ..ExerciseOutput::default()
             })
+        };
+        let source = format!(
+            "{}\n#[cfg(test)]\nmod {HIDDEN_TESTS_MODULE} {{\nuse super::*;\n{}\n}}\n",
+            read(&self.path)?,
+            read(hidden_tests)?
+        );
+        let build_dir = BuildDir::new().expect("Failed to create a build directory!");
+
+        let cmd = limits::compiler_output_with_input(
+            compiler("rustc", &build_dir)
+                .args(["--test", "-"])
+                .arg("-o")
+                .arg(build_dir.binary())
+                .args(RUSTC_JSON_ARGS)
+                .args(RUSTC_EDITION_ARGS),
+            source,
+            &CancelToken::default(),
+        )
+        .expect("Failed to run 'compile' command.");
+
+        if cmd.timed_out {
+            Err(cmd.into())
+        } else if cmd.output.status.success() {
+            Ok(CompiledExercise {
+                exercise: self,
+                build_dir,
+                test_filter: Some(HIDDEN_TESTS_MODULE),
+                cancel: CancelToken::default(),
+            })
+        } else {
+            Err(compile_error(cmd.output))
+        }
+    }
+
+    // The files whose contents decide the result of the exercise
+    pub fn source_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![self.path.clone()];
+        let build_script = self.path.with_file_name(BUILD_SCRIPT_FILE_NAME);
+        if let (Mode::BuildScript, true) = (self.mode, build_script.exists()) {
+            files.push(build_script);
         }
+        files
     }
 
-    fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        let arg = match self.mode {
-            Mode::Test => "--show-output",
-            Mode::BuildScript => return Ok(ExerciseOutput {
-                stdout: "".to_string(),
-                stderr: "".to_string(),
-            }),
-            _ => "",
+    // Write the Cargo.toml of the cargo based modes into the build directory.
+    // The manifest refers to the exercise (and its build script) where they are,
+    // so nothing has to be copied and nothing in `exercises/` is written to.
+    fn write_manifest(&self, source: &Path, build_dir: &BuildDir) {
+        let mut cargo_toml = format!(
+            r#"[package]
+name = "{}"
+version = "0.0.1"
+edition = "2021"
+"#,
+            self.name
+        );
+        let build_script = source.with_file_name(BUILD_SCRIPT_FILE_NAME);
+        if let (Mode::BuildScript, true) = (self.mode, build_script.exists()) {
+            cargo_toml += &format!("build = {}\n", toml_path(&build_script));
+        }
+        cargo_toml += &format!(
+            r#"[[bin]]
+name = "{}"
+path = {}
+[workspace]
+"#,
+            self.name,
+            toml_path(source)
+        );
+
+        let cargo_toml_error_msg = match (self.mode, !reporter::use_emoji()) {
+            (Mode::Clippy, true) => "Failed to write Clippy Cargo.toml file.",
+            (Mode::Clippy, false) => "Failed to write 📎 Clippy 📎 Cargo.toml file.",
+            _ => "Failed to write Cargo.toml file.",
         };
-        println!("pa={}", temp_file());
-        let cmd = Command::new(&temp_file())
-            .arg(arg)
-            .output()
-            .expect("Failed to run 'run' command");
+        fs::write(build_dir.manifest(), cargo_toml).expect(cargo_toml_error_msg);
+    }
 
-        let output = ExerciseOutput {
-            stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+    fn run(
+        &self,
+        build_dir: &BuildDir,
+        test_args: Vec<&str>,
+        cancel: &CancelToken,
+    ) -> Result<ExerciseOutput, ExerciseOutput> {
+        let mut cmd = match self.mode {
+            Mode::Test => {
+                let mut cmd = Command::new(build_dir.binary());
+                cmd.arg("--show-output").args(test_args);
+                cmd
+            }
+            // Cargo has to run the tests itself, as it also
+            // passes the environment set by the build script
+            Mode::BuildScript => {
+                let mut cmd = Command::new("cargo");
+                cmd.args(["test", "--manifest-path"])
+                    .arg(build_dir.manifest())
+                    .arg("--target-dir")
+                    .arg(build_dir.target_dir())
+                    .args(["--", "--show-output"])
+                    .args(test_args);
+                cmd
+            }
+            Mode::Compile | Mode::Clippy => Command::new(build_dir.binary()),
         };
+        sandbox::confine(&mut cmd, &build_dir.path);
+        let output = output_with_limits(&mut cmd, &self.limits(), cancel)
+            .expect("Failed to run 'run' command");
 
-        if cmd.status.success() {
-            Ok(output)
+        if output.output.status.success() {
+            Ok(output.into())
         } else {
-            Err(output)
+            Err(output.into())
+        }
+    }
+
+    // The hints of `error_hints` that match the diagnostics or failed tests
+    // in the output, in the order they came up
+    pub fn error_hints_for(&self, output: &ExerciseOutput) -> Vec<&str> {
+        let failed_tests = failed_tests(&output.stdout);
+        let mut hints = Vec::new();
+        let codes = output.diagnostics.iter().filter_map(|d| d.code.as_deref());
+        for key in codes.chain(failed_tests.iter().map(String::as_str)) {
+            // `tests::moves` is matched by both `tests::moves` and `moves`
+            let hint = self.error_hints.iter().find(|(name, _)| {
+                key.strip_suffix(name.as_str())
+                    .is_some_and(|module| module.is_empty() || module.ends_with("::"))
+            });
+            if let Some((_, hint)) = hint {
+                if !hints.contains(&hint.as_str()) {
+                    hints.push(hint.as_str());
+                }
+            }
+        }
+        hints
+    }
+
+    pub fn weight(&self) -> u32 {
+        self.weight.unwrap_or(1)
+    }
+
+    // Whether the tests of the exercise differ from the ones it came with.
+    // Exercises without a fingerprint in info.toml are never considered changed.
+    pub fn tests_changed(&self) -> bool {
+        match &self.test_fingerprint {
+            Some(expected) => match fs::read_to_string(&self.path) {
+                Ok(source) => test_fingerprint(&source) != *expected,
+                Err(_) => true,
+            },
+            None => false,
         }
     }
 
+    // The category of the exercise is the directory it's in, e.g. `variables`
+    // for exercises/variables/variables1.rs. Quizzes don't have one.
+    pub fn category(&self) -> Option<String> {
+        let components: Vec<&str> = self
+            .path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+        match components[..] {
+            ["exercises", category, _, ..] => Some(category.to_string()),
+            _ => None,
+        }
+    }
+
+    // The resources the exercise may use while running
+    pub fn limits(&self) -> Limits {
+        let timeout = match self.timeout {
+            Some(secs) => Duration::from_secs(secs),
+            None => limits::default_timeout(),
+        };
+        Limits::new(timeout)
+    }
+
     pub fn state(&self) -> State {
         let mut source_file =
             File::open(&self.path).expect("We were unable to open the exercise file!");
@@ -265,6 +640,10 @@ path = "{}.rs""#,
                 line: line.to_string(),
                 number: i + 1,
                 important: i == matched_line_index,
+                marker_column: re
+                    .captures(line)
+                    .and_then(|captures| captures.get(1))
+                    .map(|marker| line[..marker.start()].chars().count() + 1),
             })
             .collect();
 
@@ -272,14 +651,44 @@ path = "{}.rs""#,
     }
 
     // Check that the exercise looks to be solved using self.state()
-    // This is not the best way to check since
+    // This is not the best way to check on its own, since
     // the user can just remove the "I AM NOT DONE" string from the file
     // without actually having solved anything.
-    // The only other way to truly check this would to compile and run
-    // the exercise; which would be both costly and counterintuitive
+    // `ProgressState::is_done` additionally checks that it passed when it was verified.
     pub fn looks_done(&self) -> bool {
         self.state() == State::Done
     }
+
+    // Whether the exercise has tests marked `#[ignore = "bonus"]`, which only
+    // run when asked for and never keep the exercise from being done
+    pub fn has_bonus_tests(&self) -> bool {
+        let re = Regex::new(BONUS_TEST_REGEX).unwrap();
+        matches!(self.mode, Mode::Test | Mode::BuildScript)
+            && fs::read_to_string(&self.path).is_ok_and(|source| re.is_match(&source))
+    }
+}
+
+#[cfg(test)]
+impl Exercise {
+    // An exercise with nothing but a name, a path and a mode, for tests
+    pub fn for_test(name: &str, path: impl Into<PathBuf>, mode: Mode) -> Exercise {
+        Exercise {
+            name: name.to_string(),
+            path: path.into(),
+            mode,
+            hint: Hint::default(),
+            timeout: None,
+            tags: Vec::new(),
+            difficulty: None,
+            book_chapters: Vec::new(),
+            estimated_minutes: None,
+            test_fingerprint: None,
+            editable_tests: false,
+            hidden_tests: None,
+            weight: None,
+            error_hints: BTreeMap::new(),
+        }
+    }
 }
 
 impl Display for Exercise {
@@ -288,38 +697,100 @@ impl Display for Exercise {
     }
 }
 
-#[inline]
-fn clean() {
-    let _ignored = remove_file(&temp_file());
+// The version of the rustc that compiles the exercises, e.g. "rustc 1.70.0 (90c541806 2023-05-31)"
+pub fn rustc_version() -> Option<String> {
+    let output = Command::new("rustc").arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// The names of the failed tests in the output of a libtest harness
+pub fn failed_tests(stdout: &str) -> Vec<String> {
+    let re = Regex::new(FAILED_TEST_REGEX).unwrap();
+    re.captures_iter(stdout)
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+// A command that runs the compiler on the exercise in the build directory.
+// Build scripts and macros like `include_str!` or `env!` run student code or
+// read files and the environment at compile time, so the compiler is confined
+// to the same sandbox as the exercise itself.
+fn compiler(program: &str, build_dir: &BuildDir) -> Command {
+    let mut cmd = Command::new(program);
+    sandbox::confine(&mut cmd, &build_dir.path);
+    cmd
+}
+
+// The output of a failed compilation, with the compiler's errors rendered by
+// `diagnostics::render`. Failures that aren't errors in the code, like a
+// build script panicking, are kept as the compiler printed them.
+fn compile_error(output: Output) -> ExerciseOutput {
+    let mut output = ExerciseOutput::from(output);
+    // Cargo writes the diagnostics to stdout, rustc to stderr
+    output.diagnostics = diagnostics::parse(&format!("{}\n{}", output.stdout, output.stderr));
+    if output.diagnostics.iter().any(Diagnostic::is_error) {
+        output.stderr = diagnostics::render(&output.diagnostics);
+    }
+    output.stdout.clear();
+    output
+}
+
+// The flags that exercises of the given mode are built with
+pub fn build_flags(mode: Mode) -> Vec<&'static str> {
+    let mut flags = [RUSTC_JSON_ARGS, RUSTC_EDITION_ARGS].concat();
+    if let Mode::Clippy = mode {
+        flags.extend_from_slice(CLIPPY_ARGS);
+    }
+    flags
+}
+
+// Quote a path so it can be used as a string in a Cargo.toml
+fn toml_path(path: &Path) -> String {
+    toml::Value::String(path.display().to_string()).to_string()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::path::Path;
 
     #[test]
     fn test_clean() {
-        File::create(&temp_file()).unwrap();
-        let exercise = Exercise {
-            name: String::from("example"),
-            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::from(""),
-        };
+        let exercise = Exercise::for_test(
+            "example",
+            "tests/fixture/state/pending_exercise.rs",
+            Mode::Compile,
+        );
         let compiled = exercise.compile().unwrap();
+        let build_dir = compiled.build_dir.path.clone();
+        assert!(build_dir.exists());
         drop(compiled);
-        assert!(!Path::new(&temp_file()).exists());
+        assert!(!build_dir.exists());
+    }
+
+    #[test]
+    fn test_separate_build_dirs() {
+        let exercise = Exercise::for_test(
+            "example",
+            "tests/fixture/state/pending_exercise.rs",
+            Mode::Compile,
+        );
+        let first = exercise.compile().unwrap();
+        let second = exercise.compile().unwrap();
+        assert_ne!(first.build_dir.path, second.build_dir.path);
+        assert!(first.run().is_ok());
+        assert!(second.run().is_ok());
     }
 
     #[test]
     fn test_pending_state() {
-        let exercise = Exercise {
-            name: "pending_exercise".into(),
-            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
-        };
+        let exercise = Exercise::for_test(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+            Mode::Compile,
+        );
 
         let state = exercise.state();
         let expected = vec![
@@ -327,26 +798,31 @@ mod test {
                 line: "// fake_exercise".to_string(),
                 number: 1,
                 important: false,
+                marker_column: None,
             },
             ContextLine {
                 line: "".to_string(),
                 number: 2,
                 important: false,
+                marker_column: None,
             },
             ContextLine {
                 line: "// I AM NOT DONE".to_string(),
                 number: 3,
                 important: true,
+                marker_column: Some(4),
             },
             ContextLine {
                 line: "".to_string(),
                 number: 4,
                 important: false,
+                marker_column: None,
             },
             ContextLine {
                 line: "fn main() {".to_string(),
                 number: 5,
                 important: false,
+                marker_column: None,
             },
         ];
 
@@ -355,25 +831,110 @@ mod test {
 
     #[test]
     fn test_finished_exercise() {
-        let exercise = Exercise {
-            name: "finished_exercise".into(),
-            path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
-        };
+        let exercise = Exercise::for_test(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+            Mode::Compile,
+        );
 
         assert_eq!(exercise.state(), State::Done);
     }
 
+    #[test]
+    fn test_category() {
+        let category = |path: &str| Exercise::for_test("example", path, Mode::Compile).category();
+        assert_eq!(
+            category("exercises/variables/variables1.rs"),
+            Some(String::from("variables"))
+        );
+        assert_eq!(
+            category("./exercises/tests/build.rs"),
+            Some(String::from("tests"))
+        );
+        assert_eq!(category("exercises/quiz1.rs"), None);
+        assert_eq!(category("compSuccess.rs"), None);
+    }
+
+    #[test]
+    fn test_difficulty_from_str() {
+        assert_eq!("Hard".parse(), Ok(Difficulty::Hard));
+        assert!("impossible".parse::<Difficulty>().is_err());
+    }
+
+    #[test]
+    fn test_build_script_runs_in_sandbox() {
+        if !sandbox::enabled() {
+            return;
+        }
+        let dir = env::temp_dir().join(format!("rustlings_build_script_{}", process::id()));
+        let escaped = dir.join("escaped");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(BUILD_SCRIPT_FILE_NAME),
+            format!(
+                "fn main() {{ let _ = std::fs::write({:?}, \"\"); }}",
+                escaped
+            ),
+        )
+        .unwrap();
+        fs::write(dir.join("exercise.rs"), "#[test]\nfn passes() {}\n").unwrap();
+
+        let exercise =
+            Exercise::for_test("build_script", dir.join("exercise.rs"), Mode::BuildScript);
+        let compiled = exercise.compile().is_ok();
+        let escaped = escaped.exists();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(compiled);
+        assert!(!escaped);
+    }
+
     #[test]
     fn test_exercise_with_output() {
-        let exercise = Exercise {
-            name: "exercise_with_output".into(),
-            path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
-            mode: Mode::Test,
-            hint: String::new(),
-        };
+        let exercise = Exercise::for_test(
+            "exercise_with_output",
+            "tests/fixture/success/testSuccess.rs",
+            Mode::Test,
+        );
         let out = exercise.compile().unwrap().run().unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
     }
+
+    #[test]
+    fn test_error_hints_for() {
+        let hints = [
+            ("E0382", "moved"),
+            ("clippy::float_cmp", "floats"),
+            ("moves_value", "test"),
+            ("value", "not a test"),
+        ];
+        let exercise = Exercise {
+            error_hints: hints
+                .iter()
+                .map(|(key, hint)| (key.to_string(), hint.to_string()))
+                .collect(),
+            ..Exercise::for_test(
+                "error_hints",
+                "tests/fixture/success/testSuccess.rs",
+                Mode::Test,
+            )
+        };
+        let diagnostic = |code: &str| Diagnostic {
+            code: Some(code.to_string()),
+            ..Diagnostic::default()
+        };
+        let output = ExerciseOutput {
+            stdout: String::from("test tests::moves_value ... FAILED\n"),
+            diagnostics: vec![
+                diagnostic("E0308"),
+                diagnostic("clippy::float_cmp"),
+                diagnostic("E0382"),
+                diagnostic("E0382"),
+            ],
+            ..ExerciseOutput::default()
+        };
+        assert_eq!(
+            exercise.error_hints_for(&output),
+            ["floats", "moved", "test"]
+        );
+    }
 }
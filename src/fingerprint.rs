@@ -0,0 +1,280 @@
+// Fingerprints of the tests that come with an exercise.
+//
+// A test exercise can be made to pass by deleting or weakening its tests.
+// To notice that, info.toml holds a fingerprint of the original tests: the
+// hash of the tokens of every `#[cfg(test)]` and `#[test]` item in the file.
+// Working on tokens means that formatting and comments don't matter, only
+// changes to the code of the tests do.
+
+use crate::digest::sha256_hex;
+
+// The fingerprint of the tests in the given source
+pub fn test_fingerprint(source: &str) -> String {
+    sha256_hex(test_items(&tokenize(source)).join(" "))
+}
+
+// The tokens of all items at the top level of the file that are tests,
+// including their attributes
+fn test_items(tokens: &[String]) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        if depth == 0 && is_outer_attribute(tokens, i) {
+            let (attributes_end, end) = (attributes_end(tokens, i), item_end(tokens, i));
+            if is_test(&tokens[i..attributes_end]) {
+                items.extend(tokens[i..end].iter().map(String::as_str));
+            }
+            i = end;
+            continue;
+        }
+        match tokens[i].as_str() {
+            "{" | "(" | "[" => depth += 1,
+            "}" | ")" | "]" => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        i += 1;
+    }
+    items
+}
+
+fn is_outer_attribute(tokens: &[String], i: usize) -> bool {
+    tokens[i] == "#" && tokens.get(i + 1).map(String::as_str) == Some("[")
+}
+
+// The index after the attributes starting at `start`
+fn attributes_end(tokens: &[String], start: usize) -> usize {
+    let mut i = start;
+    while i < tokens.len() && is_outer_attribute(tokens, i) {
+        let mut depth = 0usize;
+        for (j, token) in tokens.iter().enumerate().skip(i + 1) {
+            match token.as_str() {
+                "[" => depth += 1,
+                "]" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        i = j + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if depth > 0 {
+            return tokens.len();
+        }
+    }
+    i
+}
+
+// Whether the attributes contain `#[test]` or `#[cfg(test)]`
+fn is_test(attributes: &[String]) -> bool {
+    attributes.windows(3).any(|w| w == ["[", "test", "]"])
+        || attributes
+            .windows(4)
+            .any(|w| w == ["cfg", "(", "test", ")"])
+}
+
+// The index after the item starting at `start`: either its closing brace,
+// or the semicolon that ends it (e.g. `mod tests;` or `use ...;`)
+fn item_end(tokens: &[String], start: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token.as_str() {
+            "{" | "(" | "[" => depth += 1,
+            "}" | ")" | "]" => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 && token == "}" {
+                    return i + 1;
+                }
+            }
+            ";" if depth == 0 => return i + 1,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+// Split Rust source into tokens: identifiers, numbers, literals and single
+// punctuation characters. Whitespace and comments are dropped.
+fn tokenize(source: &str) -> Vec<String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            // Block comments nest in Rust
+            let mut depth = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                    depth += 1;
+                    i += 2;
+                } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            continue;
+        } else if c == '"' {
+            i = string_end(&chars, i + 1);
+        } else if c == '\'' {
+            i = char_end(&chars, i);
+        } else if c.is_alphanumeric() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            // Raw strings, e.g. r"..." or br#"..."#
+            let word: String = chars[start..i].iter().collect();
+            if (word == "r" || word == "br") && matches!(chars.get(i), Some('"' | '#')) {
+                i = raw_string_end(&chars, i);
+            }
+        } else {
+            i += 1;
+        }
+        tokens.push(chars[start..i.min(chars.len())].iter().collect());
+    }
+    tokens
+}
+
+// The index after the closing quote of a string whose contents start at `i`
+fn string_end(chars: &[char], mut i: usize) -> usize {
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+// The index after a raw string, with `i` at its first `#` or `"`
+fn raw_string_end(chars: &[char], mut i: usize) -> usize {
+    let hashes = chars[i..].iter().take_while(|&&c| c == '#').count();
+    i += hashes + 1;
+    while i < chars.len() {
+        if chars[i] == '"'
+            && chars[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&c| c == '#')
+                .count()
+                == hashes
+        {
+            return i + 1 + hashes;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+// The index after a character literal like 'a' or '\n' starting at `i`.
+// Lifetimes like 'a are just the quote on their own.
+fn char_end(chars: &[char], i: usize) -> usize {
+    match (chars.get(i + 1), chars.get(i + 2)) {
+        (Some('\\'), _) => {
+            let mut end = i + 2;
+            while end < chars.len() && chars[end] != '\'' {
+                end += 1;
+            }
+            end + 1
+        }
+        (Some(_), Some('\'')) => i + 3,
+        _ => i + 1,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: &str = r#"
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds() {
+        assert_eq!(add(1, 2), 3, "1 + 2 is {}", "3");
+    }
+}
+"#;
+
+    #[test]
+    fn test_formatting_and_comments_dont_matter() {
+        let reformatted = SOURCE
+            .replace(
+                "assert_eq!(add(1, 2), 3,",
+                "// Check it\n        assert_eq!( add(1,2),3 ,",
+            )
+            .replace("use super::*;", "/* everything */ use super :: *;");
+        assert_eq!(test_fingerprint(SOURCE), test_fingerprint(&reformatted));
+    }
+
+    #[test]
+    fn test_code_outside_of_tests_doesnt_matter() {
+        let solved = SOURCE.replace("a + b", "b + a");
+        assert_eq!(test_fingerprint(SOURCE), test_fingerprint(&solved));
+    }
+
+    #[test]
+    fn test_changed_tests() {
+        let weakened = SOURCE.replace("3, \"1 + 2", "add(1, 2), \"1 + 2");
+        let ignored = SOURCE.replace("#[test]", "#[test]\n    #[ignore]");
+        let removed = SOURCE.replace("#[cfg(test)]", "");
+        let in_string = SOURCE.replace("\"1 + 2 is {}\"", "\"1  +  2 is {}\"");
+        for changed in [weakened, ignored, removed, in_string] {
+            assert_ne!(test_fingerprint(SOURCE), test_fingerprint(&changed));
+        }
+    }
+
+    #[test]
+    fn test_top_level_tests() {
+        let source = "fn f() {}\n#[test]\nfn g() { assert!(true) }\n";
+        let tokens = tokenize(source);
+        assert_eq!(
+            test_items(&tokens),
+            [
+                "#", "[", "test", "]", "fn", "g", "(", ")", "{", "assert", "!", "(", "true", ")",
+                "}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_literals() {
+        assert_eq!(
+            tokenize(r##"'a' '\n' x<'a> r#"a "b""# "\"""##),
+            [
+                "'a'",
+                r"'\n'",
+                "x",
+                "<",
+                "'",
+                "a",
+                ">",
+                r##"r#"a "b""#"##,
+                r#""\"""#
+            ]
+        );
+    }
+}
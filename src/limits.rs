@@ -0,0 +1,330 @@
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How long an exercise may run unless info.toml or `--timeout` say otherwise
+pub const DEFAULT_TIMEOUT_SECS: u64 = 10;
+// The most memory an exercise may allocate (Linux only)
+const MEMORY_LIMIT_BYTES: u64 = 1024 * 1024 * 1024;
+// How long compiling an exercise may take. A build script can loop forever
+// just like an exercise can, but compiling is never limited otherwise.
+pub const COMPILE_TIMEOUT: Duration = Duration::from_secs(60);
+// How often a running exercise is checked for having finished
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+static DEFAULT_TIMEOUT: AtomicU64 = AtomicU64::new(DEFAULT_TIMEOUT_SECS);
+
+// Change the timeout of exercises that don't have their own in info.toml
+pub fn set_default_timeout(secs: u64) {
+    DEFAULT_TIMEOUT.store(secs, Ordering::SeqCst);
+}
+
+pub fn default_timeout() -> Duration {
+    Duration::from_secs(DEFAULT_TIMEOUT.load(Ordering::SeqCst))
+}
+
+// Stops the commands that are run with it, e.g. because watch mode noticed
+// another change and starts verifying afresh. Every verification has a token
+// of its own, so cancelling one leaves everything else running.
+#[derive(Clone, Default, Debug)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    // Kill the command that is running with this token right now,
+    // and any that is started with it from now on
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    // Whether the commands were cancelled, which means that
+    // whatever they output doesn't say anything about the exercise
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// The resources a running exercise may use
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    // The wall-clock time after which the exercise is killed
+    pub timeout: Duration,
+    // The memory the exercise may allocate, enforced with RLIMIT_DATA
+    pub memory_bytes: u64,
+}
+
+impl Limits {
+    pub fn new(timeout: Duration) -> Limits {
+        Limits {
+            timeout,
+            memory_bytes: MEMORY_LIMIT_BYTES,
+        }
+    }
+
+    // The CPU time limit, enforced with RLIMIT_CPU. It counts the time of
+    // all threads, so it's scaled by the number of cores: the wall-clock
+    // timeout always kicks in first, this only catches anything that slips
+    // through, e.g. a process that outlives rustlings.
+    fn cpu_secs(&self) -> u64 {
+        let cores = thread::available_parallelism().map_or(1, |n| n.get() as u64);
+        (self.timeout.as_secs() + 1) * cores
+    }
+}
+
+// The output of a command run with `output_with_limits`
+pub struct LimitedOutput {
+    pub output: Output,
+    // Whether the command was killed because it ran into the timeout
+    pub timed_out: bool,
+}
+
+// Run the command to completion like `Command::output` does, but kill it
+// (together with any processes it started) once it runs into the timeout
+// or is cancelled.
+pub fn output_with_limits(
+    cmd: &mut Command,
+    limits: &Limits,
+    cancel: &CancelToken,
+) -> io::Result<LimitedOutput> {
+    apply_limits(cmd, limits);
+    output_until(cmd, Instant::now() + limits.timeout, None, cancel)
+}
+
+// Run the command to completion like `Command::output` does, but kill it
+// (together with any processes it started) if it's cancelled or runs into
+// `COMPILE_TIMEOUT`. Meant for the compiler, which shouldn't be limited like
+// the exercises are.
+pub fn compiler_output(cmd: &mut Command, cancel: &CancelToken) -> io::Result<LimitedOutput> {
+    own_process_group(cmd);
+    output_until(cmd, Instant::now() + COMPILE_TIMEOUT, None, cancel)
+}
+
+// Like `compiler_output`, with `input` on the compiler's stdin
+pub fn compiler_output_with_input(
+    cmd: &mut Command,
+    input: String,
+    cancel: &CancelToken,
+) -> io::Result<LimitedOutput> {
+    own_process_group(cmd);
+    output_until(cmd, Instant::now() + COMPILE_TIMEOUT, Some(input), cancel)
+}
+
+fn output_until(
+    cmd: &mut Command,
+    deadline: Instant,
+    input: Option<String>,
+    cancel: &CancelToken,
+) -> io::Result<LimitedOutput> {
+    cmd.stdin(if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    })
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+
+    let mut child = cmd.spawn()?;
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        thread::spawn(move || stdin.write_all(input.as_bytes()));
+    }
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if cancel.is_cancelled() {
+            kill(&mut child);
+            break child.wait()?;
+        }
+        if Instant::now() >= deadline {
+            timed_out = true;
+            kill(&mut child);
+            break child.wait()?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    Ok(LimitedOutput {
+        output: Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        },
+        timed_out,
+    })
+}
+
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ignored = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+// Put the child into its own process group (so it can be killed with
+// everything it spawned, e.g. the test binary that `cargo test` runs),
+// and limit its memory and CPU time on Linux.
+#[cfg(unix)]
+fn apply_limits(cmd: &mut Command, limits: &Limits) {
+    own_process_group(cmd);
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::process::CommandExt;
+
+        let (memory_bytes, cpu_secs) = (limits.memory_bytes, limits.cpu_secs());
+        // SAFETY: getrlimit and setrlimit are async-signal-safe and nothing is allocated here
+        unsafe {
+            cmd.pre_exec(move || {
+                set_rlimit(libc::RLIMIT_DATA, memory_bytes)?;
+                set_rlimit(libc::RLIMIT_CPU, cpu_secs)
+            });
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = limits;
+}
+
+#[cfg(not(unix))]
+fn apply_limits(_cmd: &mut Command, _limits: &Limits) {}
+
+#[cfg(unix)]
+fn own_process_group(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+
+    cmd.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn own_process_group(_cmd: &mut Command) {}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(all(target_os = "linux", not(target_env = "gnu")))]
+type Resource = libc::c_int;
+
+// Lower both the soft and the hard limit of the resource to the value.
+// Limits that are already lower than that are left alone.
+#[cfg(target_os = "linux")]
+fn set_rlimit(resource: Resource, value: u64) -> io::Result<()> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: the pointers are valid for the duration of the calls
+    unsafe {
+        if libc::getrlimit(resource, &mut limit) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let value = (value as libc::rlim_t).min(limit.rlim_max);
+        limit.rlim_cur = value;
+        limit.rlim_max = value;
+        if libc::setrlimit(resource, &limit) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn kill(child: &mut Child) {
+    // The child is the leader of its process group, so this reaches its children too
+    // SAFETY: kill has no memory safety requirements
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut Child) {
+    let _ignored = child.kill();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_timeout() {
+        let limits = Limits::new(Duration::from_millis(200));
+        let start = Instant::now();
+        let result = output_with_limits(
+            Command::new("sleep").arg("10"),
+            &limits,
+            &CancelToken::default(),
+        )
+        .unwrap();
+        assert!(result.timed_out);
+        assert!(!result.output.status.success());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_kills_grandchildren() {
+        let limits = Limits::new(Duration::from_millis(200));
+        let start = Instant::now();
+        // The grandchild keeps stdout open, so reading it only finishes once it was killed too
+        let result = output_with_limits(
+            Command::new("sh").args(["-c", "sleep 10; true"]),
+            &limits,
+            &CancelToken::default(),
+        )
+        .unwrap();
+        assert!(result.timed_out);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_cancel_only_stops_its_own_commands() {
+        let limits = Limits::new(Duration::from_secs(10));
+        let (cancel, other) = (CancelToken::default(), CancelToken::default());
+        let cancelled = thread::spawn({
+            let cancel = cancel.clone();
+            move || output_with_limits(Command::new("sleep").arg("10"), &limits, &cancel)
+        });
+        let finished = thread::spawn({
+            let other = other.clone();
+            move || output_with_limits(Command::new("sleep").arg("1"), &limits, &other)
+        });
+        thread::sleep(Duration::from_millis(200));
+        cancel.cancel();
+
+        let cancelled = cancelled.join().unwrap().unwrap();
+        assert!(!cancelled.output.status.success() && !cancelled.timed_out);
+        assert!(finished.join().unwrap().unwrap().output.status.success());
+        assert!(!other.is_cancelled());
+    }
+
+    #[test]
+    fn test_cpu_limit_leaves_room_for_every_core() {
+        let limits = Limits::new(Duration::from_secs(10));
+        let cores = thread::available_parallelism().unwrap().get() as u64;
+        assert!(limits.cpu_secs() >= limits.timeout.as_secs() * cores);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_finishes_in_time() {
+        let limits = Limits::new(Duration::from_secs(10));
+        let result = output_with_limits(
+            Command::new("echo").arg("hi"),
+            &limits,
+            &CancelToken::default(),
+        )
+        .unwrap();
+        assert!(!result.timed_out);
+        assert!(result.output.status.success());
+        assert_eq!(result.output.stdout, b"hi\n");
+    }
+}
@@ -1,31 +1,47 @@
-use crate::exercise::{Exercise, ExerciseList};
+use crate::cicv::cicvverify;
+use crate::exercise::{Difficulty, Exercise, ExerciseList};
+use crate::limits::CancelToken;
 use crate::project::RustAnalyzerProject;
-use crate::run::{reset, run};
-use crate::verify::verify;
+use crate::reporter::{reporter, Format};
+use crate::run::{hint, reset, run};
+use crate::state::ProgressState;
+use crate::verify::{test_bonus, verify, verify_events};
 use argh::FromArgs;
 use console::Emoji;
 use notify::DebouncedEvent;
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
 use std::fs;
-use std::io::{self, prelude::*};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::Ordering;
 use std::sync::mpsc::{channel, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
-use std::time::{UNIX_EPOCH, SystemTime};
 
 #[macro_use]
 mod ui;
 
+mod book;
+mod cache;
+mod check_info;
+mod cicv;
+mod diagnostics;
+mod digest;
 mod exercise;
+mod fingerprint;
+mod limits;
+mod originals;
+mod pool;
 mod project;
+mod reporter;
 mod run;
+mod sandbox;
+mod shell;
+mod state;
+mod tui;
 mod verify;
+mod watcher;
 
 // In sync with crate version
 const VERSION: &str = "5.5.1";
@@ -39,6 +55,22 @@ struct Args {
     /// show the executable version
     #[argh(switch, short = 'v')]
     version: bool,
+    /// the number of seconds an exercise may run before it is stopped,
+    /// unless info.toml says otherwise (defaults to 10)
+    #[argh(option)]
+    timeout: Option<u64>,
+    /// run exercises without isolating them from the network and file system
+    #[argh(switch)]
+    no_sandbox: bool,
+    /// consider exercises done once their `I AM NOT DONE` comment is removed,
+    /// without checking that they actually passed
+    #[argh(switch)]
+    legacy_marker: bool,
+    /// how to report what's going on: human, plain (no colors, emoji or
+    /// spinners) or json (one event per line). Defaults to plain when
+    /// NO_COLOR is set, and to human otherwise
+    #[argh(option)]
+    format: Option<Format>,
     #[argh(subcommand)]
     nested: Option<Subcommands>,
 }
@@ -53,17 +85,81 @@ enum Subcommands {
     Hint(HintArgs),
     List(ListArgs),
     Lsp(LspArgs),
-    CicvVerify(CicvVerifyArgs)
+    Book(BookArgs),
+    CicvVerify(CicvVerifyArgs),
+    VerifyReport(VerifyReportArgs),
+    CheckInfo(CheckInfoArgs),
+    Cache(CacheArgs),
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "cicvverify", description = "cicvverify")]
-struct CicvVerifyArgs {}
+struct CicvVerifyArgs {
+    #[argh(option, short = 'j')]
+    /// the number of exercises to grade at the same time
+    /// (defaults to the number of CPUs)
+    jobs: Option<usize>,
+    #[argh(option, short = 'o')]
+    /// where to write the results to
+    /// (defaults to .github/result/check_result.json)
+    output: Option<PathBuf>,
+    #[argh(option)]
+    /// the name of the student to put into the results
+    /// (defaults to $RUSTLINGS_USER_NAME or `git config user.name`)
+    user_name: Option<String>,
+    #[argh(option)]
+    /// the directory with the hidden tests of the exercises,
+    /// which are graded in addition to their own tests
+    hidden_tests: Option<PathBuf>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "verify-report")]
+/// Checks the signature of a cicvverify report and the graded files against this checkout
+struct VerifyReportArgs {
+    #[argh(positional)]
+    /// the report to check (defaults to .github/result/check_result.json)
+    path: Option<PathBuf>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "cache")]
+/// Manages the cached results of verifying exercises
+struct CacheArgs {
+    #[argh(subcommand)]
+    nested: CacheSubcommands,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+enum CacheSubcommands {
+    Clear(CacheClearArgs),
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "clear")]
+/// Removes all cached results, so every exercise is compiled again
+struct CacheClearArgs {}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "check-info")]
+/// Checks info.toml for mistakes and that it matches the exercises
+struct CheckInfoArgs {
+    #[argh(option, short = 'j')]
+    /// the number of exercises to check at the same time
+    /// (defaults to the number of CPUs)
+    jobs: Option<usize>,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "verify")]
 /// Verifies all exercises according to the recommended order
-struct VerifyArgs {}
+struct VerifyArgs {
+    /// how to report the results: human, plain or json, which reports what
+    /// happens to every exercise as one JSON event per line
+    #[argh(option)]
+    message_format: Option<Format>,
+}
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "watch")]
@@ -72,6 +168,13 @@ struct WatchArgs {
     /// show hints on success
     #[argh(switch)]
     success_hints: bool,
+    /// use a full-screen terminal UI with single-key commands
+    #[argh(switch)]
+    tui: bool,
+    /// check the exercises for changes every second instead of relying on
+    /// notifications from the file system, e.g. on network file systems
+    #[argh(switch)]
+    poll: bool,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
@@ -81,24 +184,42 @@ struct RunArgs {
     #[argh(positional)]
     /// the name of the exercise
     name: String,
+    #[argh(switch)]
+    /// run the bonus tests of the exercise as well
+    bonus: bool,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "reset")]
-/// Resets a single exercise using "git stash -- <filename>"
+/// Resets a single exercise to its original version
 struct ResetArgs {
     #[argh(positional)]
     /// the name of the exercise
     name: String,
+    #[argh(switch)]
+    /// bring back your version from before the last reset
+    undo: bool,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "hint")]
-/// Returns a hint for the given exercise
+/// Returns a hint for the given exercise, revealing the next one each time
 struct HintArgs {
     #[argh(positional)]
     /// the name of the exercise
     name: String,
+    #[argh(switch)]
+    /// show all hints at once
+    all: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "book")]
+/// Shows the sections of the Rust Book that belong to the given exercise
+struct BookArgs {
+    #[argh(positional)]
+    /// the name of the exercise
+    name: String,
 }
 
 #[derive(FromArgs, PartialEq, Debug)]
@@ -126,210 +247,274 @@ struct ListArgs {
     #[argh(switch, short = 's')]
     /// display only exercises that have been solved
     solved: bool,
+    #[argh(option, short = 't')]
+    /// display only exercises with the given tag
+    tag: Option<String>,
+    #[argh(option, short = 'd')]
+    /// display only exercises of the given difficulty (easy, medium or hard)
+    difficulty: Option<Difficulty>,
+    #[argh(switch)]
+    /// show how far along you are in each category instead of every exercise
+    summary: bool,
 }
 
-#[derive(Deserialize, Serialize)]
-pub struct ExerciseCheckList {
-    pub exercises: Vec<ExerciseResult>,
-    pub user_name: Option<String>,
-    pub statistics: ExerciseStatistics
-}
-
-#[derive(Deserialize, Serialize)]
-pub struct ExerciseResult {
-    pub name: String,
-    pub result: bool
-}
-
-#[derive(Deserialize, Serialize)]
-pub struct  ExerciseStatistics {
-    pub total_exercations: usize,
-    pub total_succeeds: usize,
-    pub total_failures: usize,
-    pub total_time: u32,
-}
-
-#[tokio::main]
-async fn main() {
-    let args: Args = argh::from_env();
+fn main() {
+    let args = args_from_env();
+    let message_format = match &args.nested {
+        Some(Subcommands::Verify(subargs)) => subargs.message_format,
+        _ => None,
+    };
+    reporter::set_format(
+        message_format
+            .or(args.format)
+            .unwrap_or_else(Format::from_env),
+    );
 
     if args.version {
-        println!("v{VERSION}");
+        reporter().info(&format!("v{VERSION}"));
         std::process::exit(0);
     }
 
     if args.nested.is_none() {
-        println!("\n{WELCOME}\n");
+        reporter().info(&format!("\n{WELCOME}\n"));
     }
 
     if !Path::new("info.toml").exists() {
-        println!(
+        reporter().info(&format!(
             "{} must be run from the rustlings directory",
             std::env::current_exe().unwrap().to_str().unwrap()
-        );
-        println!("Try `cd rustlings/`!");
+        ));
+        reporter().info("Try `cd rustlings/`!");
         std::process::exit(1);
     }
 
     if !rustc_exists() {
-        println!("We cannot find `rustc`.");
-        println!("Try running `rustc --version` to diagnose your problem.");
-        println!("For instructions on how to install Rust, check the README.");
+        reporter().info("We cannot find `rustc`.");
+        reporter().info("Try running `rustc --version` to diagnose your problem.");
+        reporter().info("For instructions on how to install Rust, check the README.");
         std::process::exit(1);
     }
 
+    if let Some(secs) = args.timeout {
+        limits::set_default_timeout(secs);
+    }
+    if args.no_sandbox {
+        sandbox::disable();
+    }
+    if matches!(
+        args.nested,
+        Some(
+            Subcommands::Verify(_)
+                | Subcommands::Watch(_)
+                | Subcommands::Run(_)
+                | Subcommands::CicvVerify(_)
+                | Subcommands::CheckInfo(_)
+        )
+    ) {
+        sandbox::warn_if_unavailable();
+    }
+
+    // Checked before info.toml is parsed, so it can explain what's wrong with it
+    if let Some(Subcommands::CheckInfo(subargs)) = &args.nested {
+        let jobs = subargs.jobs.unwrap_or_else(pool::default_jobs);
+        check_info::check_info(jobs).unwrap_or_else(|_| std::process::exit(1));
+        std::process::exit(0);
+    }
+
     let toml_str = &fs::read_to_string("info.toml").unwrap();
-    let exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
+    let exercises = match toml::from_str::<ExerciseList>(toml_str) {
+        Ok(list) => list.exercises,
+        Err(e) => {
+            reporter().info(&format!("info.toml is invalid: {e}"));
+            reporter().info("Run `rustlings check-info` to check it for mistakes.");
+            std::process::exit(1);
+        }
+    };
     let verbose = args.nocapture;
+    let mut state = ProgressState::load(args.legacy_marker);
 
     let command = args.nested.unwrap_or_else(|| {
-        println!("{DEFAULT_OUT}\n");
+        reporter().info(&format!("{DEFAULT_OUT}\n"));
         std::process::exit(0);
     });
     match command {
         Subcommands::List(subargs) => {
-            if !subargs.paths && !subargs.names {
-                println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
+            if subargs.summary {
+                reporter().info(&format!(
+                    "{:<17}\t{:>9}\t{:>9}",
+                    "Category", "Exercises", "Est. time"
+                ));
+            } else if !subargs.paths && !subargs.names {
+                reporter().info(&format!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status"));
             }
             let mut exercises_done: u16 = 0;
+            // Category, number of exercises, number done, and estimated minutes
+            let mut categories: Vec<(String, usize, usize, u32)> = Vec::new();
             let filters = subargs.filter.clone().unwrap_or_default().to_lowercase();
             exercises.iter().for_each(|e| {
                 let fname = format!("{}", e.path.display());
                 let filter_cond = filters
                     .split(',')
                     .filter(|f| !f.trim().is_empty())
-                    .any(|f| e.name.contains(&f) || fname.contains(&f));
-                let status = if e.looks_done() {
+                    .any(|f| e.name.contains(f) || fname.contains(f));
+                let tag_cond = match &subargs.tag {
+                    Some(tag) => e.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+                    None => true,
+                };
+                let difficulty_cond =
+                    subargs.difficulty.is_none() || e.difficulty == subargs.difficulty;
+                let done = state.is_done(e);
+                let status = if done {
                     exercises_done += 1;
                     "Done"
                 } else {
                     "Pending"
                 };
                 let solve_cond = {
-                    (e.looks_done() && subargs.solved)
-                        || (!e.looks_done() && subargs.unsolved)
+                    (done && subargs.solved)
+                        || (!done && subargs.unsolved)
                         || (!subargs.solved && !subargs.unsolved)
                 };
-                if solve_cond && (filter_cond || subargs.filter.is_none()) {
-                    let line = if subargs.paths {
-                        format!("{fname}\n")
-                    } else if subargs.names {
-                        format!("{}\n", e.name)
-                    } else {
-                        format!("{:<17}\t{fname:<46}\t{status:<7}\n", e.name)
+                let listed = solve_cond
+                    && tag_cond
+                    && difficulty_cond
+                    && (filter_cond || subargs.filter.is_none());
+                if !listed {
+                    return;
+                }
+                if subargs.summary {
+                    let category = e.category().unwrap_or_else(|| String::from("other"));
+                    let index = match categories.iter().position(|c| c.0 == category) {
+                        Some(index) => index,
+                        None => {
+                            categories.push((category, 0, 0, 0));
+                            categories.len() - 1
+                        }
                     };
-                    // Somehow using println! leads to the binary panicking
-                    // when its output is piped.
-                    // So, we're handling a Broken Pipe error and exiting with 0 anyway
-                    let stdout = std::io::stdout();
-                    {
-                        let mut handle = stdout.lock();
-                        handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
-                            match e.kind() {
-                                std::io::ErrorKind::BrokenPipe => std::process::exit(0),
-                                _ => std::process::exit(1),
-                            };
-                        });
-                    }
+                    let entry = &mut categories[index];
+                    entry.1 += 1;
+                    entry.2 += usize::from(done);
+                    entry.3 += e.estimated_minutes.unwrap_or_default();
+                    return;
                 }
+                let line = if subargs.paths {
+                    fname
+                } else if subargs.names {
+                    e.name.clone()
+                } else {
+                    format!("{:<17}\t{fname:<46}\t{status:<7}", e.name)
+                };
+                reporter().info(&line);
             });
+            for (category, total, done, minutes) in categories {
+                let exercises = format!("{done}/{total}");
+                let time = if minutes > 0 {
+                    format!("{minutes} min")
+                } else {
+                    String::from("-")
+                };
+                reporter().info(&format!("{category:<17}\t{exercises:>9}\t{time:>9}"));
+            }
             let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
-            println!(
+            reporter().info(&format!(
                 "Progress: You completed {} / {} exercises ({:.1} %).",
                 exercises_done,
                 exercises.len(),
                 percentage_progress
-            );
+            ));
             std::process::exit(0);
         }
 
+        Subcommands::Book(subargs) => {
+            let exercise = find_exercise(&subargs.name, &exercises, &state);
+            book::print_sections(exercise);
+        }
+
         Subcommands::Run(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
-            run(exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
+            let exercise = find_exercise(&subargs.name, &exercises, &state);
+            let state = Mutex::new(state);
+            let result = if subargs.bonus {
+                test_bonus(exercise, verbose, &state)
+            } else {
+                run(exercise, verbose, &state)
+            };
+            result.unwrap_or_else(|_| std::process::exit(1));
         }
 
         Subcommands::Reset(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &exercises, &state);
 
-            reset(exercise).unwrap_or_else(|_| std::process::exit(1));
+            reset(exercise, subargs.undo).unwrap_or_else(|_| std::process::exit(1));
         }
 
         Subcommands::Hint(subargs) => {
-            let exercise = find_exercise(&subargs.name, &exercises);
+            let exercise = find_exercise(&subargs.name, &exercises, &state);
+
+            if hint(exercise, &mut state, subargs.all) {
+                reporter().info("");
+                reporter().info(&format!(
+                    "Run `rustlings hint {}` again for the next hint, or add `--all` to see them all.",
+                    subargs.name
+                ));
+            }
+        }
 
-            println!("{}", exercise.hint);
+        Subcommands::Verify(_subargs) if reporter::format() == Format::Json => {
+            verify_events(&exercises, &mut state).unwrap_or_else(|_| std::process::exit(1));
         }
 
         Subcommands::Verify(_subargs) => {
-            verify(&exercises, (0, exercises.len()), verbose, false)
-                .unwrap_or_else(|_| std::process::exit(1));
+            let state = Mutex::new(state);
+            verify(
+                &exercises,
+                (0, exercises.len()),
+                verbose,
+                false,
+                &state,
+                &CancelToken::default(),
+            )
+            .unwrap_or_else(|_| std::process::exit(1));
         }
 
-        Subcommands::CicvVerify(_subargs) => {
-            // let toml_str = &fs::read_to_string("info.toml").unwrap();
-            // exercises = toml::from_str::<ExerciseList>(toml_str).unwrap().exercises;
-            let now_start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            let rights = Arc::new(Mutex::new(0));
-            let alls = exercises.len();
-
-            let exercise_check_list =  Arc::new(Mutex::new(
-                ExerciseCheckList {
-                    exercises: vec![], 
-                    user_name:  None, 
-                    statistics: ExerciseStatistics { 
-                        total_exercations: alls, 
-                        total_succeeds: 0, 
-                        total_failures: 0, 
-                        total_time: 0, 
-                    }
-                }
-            ));
+        Subcommands::CicvVerify(subargs) => {
+            let output = subargs
+                .output
+                .unwrap_or_else(|| PathBuf::from(cicv::DEFAULT_RESULT_PATH));
+            let user_name = cicv::user_name(subargs.user_name);
+            let jobs = subargs.jobs.unwrap_or_else(pool::default_jobs);
+            if let Err(e) = cicvverify(
+                &exercises,
+                jobs,
+                &output,
+                user_name,
+                subargs.hidden_tests.as_deref(),
+            ) {
+                reporter().info(&format!(
+                    "Failed to write the results to {}: {e}",
+                    output.display()
+                ));
+                std::process::exit(1);
+            }
+        }
 
-            let mut tasks = vec![];
-            for exercise in exercises {
-                let now_start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                let inner_exercise = exercise;
-                let c_mutex = Arc::clone(&rights);
-                let exercise_check_list_ref = Arc::clone(&exercise_check_list);
-                let _verbose = verbose.clone();
-                let t = tokio::task::spawn( async move {
-                    match run(&inner_exercise, true) {
-                    // match verify(vec![&inner_exercise], (0, 1), true, true) {
-                        Ok(_) => {
-                            *c_mutex.lock().unwrap() += 1;
-                            println!("{}执行成功", inner_exercise.name);
-                            println!("总的题目数: {}", alls);
-                            println!("当前做正确的题目数: {}", *c_mutex.lock().unwrap());
-                            let now_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                            println!("当前修改试卷耗时: {} s", now_end - now_start);
-                            exercise_check_list_ref.lock().unwrap().exercises.push(ExerciseResult{ 
-                                name: inner_exercise.name, result: true,
-                            });
-                            exercise_check_list_ref.lock().unwrap().statistics.total_succeeds += 1;
-                        },
-                        Err(_) => {
-                            println!("{}执行失败", inner_exercise.name);
-                            println!("总的题目数: {}", alls);
-                            println!("当前做正确的题目数: {}", *c_mutex.lock().unwrap());
-                            let now_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                            println!("当前修改试卷耗时: {} s", now_end - now_start);
-                            exercise_check_list_ref.lock().unwrap().exercises.push(ExerciseResult{ 
-                                name: inner_exercise.name, result: false,
-                            });
-                            exercise_check_list_ref.lock().unwrap().statistics.total_failures += 1;
-                        }
-                    }
-                });
-                tasks.push(t);
+        // Handled before info.toml is parsed
+        Subcommands::CheckInfo(_) => unreachable!(),
+
+        Subcommands::VerifyReport(subargs) => {
+            let path = subargs
+                .path
+                .unwrap_or_else(|| PathBuf::from(cicv::DEFAULT_RESULT_PATH));
+            cicv::verify_report(&path).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Cache(subargs) => match subargs.nested {
+            CacheSubcommands::Clear(_) => {
+                if let Err(e) = cache::clear() {
+                    reporter().info(&format!("Failed to clear {}: {e}", cache::CACHE_DIR));
+                    std::process::exit(1);
+                }
+                reporter().info(&format!("Cleared the cache in {}", cache::CACHE_DIR));
             }
-            for task in tasks { task.await.unwrap(); }
-            let now_end = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            let total_time = now_end - now_start;
-            println!("===============================试卷批改完成,总耗时: {} s; ==================================", total_time);
-            let exercise_check_list_ref = Arc::clone(&exercise_check_list);
-            exercise_check_list_ref.lock().unwrap().statistics.total_time = total_time as u32;
-            let serialized = serde_json::to_string_pretty(&*exercise_check_list.lock().unwrap()).unwrap();
-            fs::write(".github/result/check_result.json", serialized).unwrap();
         },
 
         Subcommands::Lsp(_subargs) => {
@@ -342,93 +527,98 @@ async fn main() {
                 .expect("Couldn't parse rustlings exercises files");
 
             if project.crates.is_empty() {
-                println!("Failed find any exercises, make sure you're in the `rustlings` folder");
+                reporter()
+                    .info("Failed find any exercises, make sure you're in the `rustlings` folder");
             } else if project.write_to_disk().is_err() {
-                println!("Failed to write rust-project.json to disk for rust-analyzer");
+                reporter().info("Failed to write rust-project.json to disk for rust-analyzer");
             } else {
-                println!("Successfully generated rust-project.json");
-                println!("rust-analyzer will now parse exercises, restart your language server or editor")
+                reporter().info("Successfully generated rust-project.json");
+                reporter().info("rust-analyzer will now parse exercises, restart your language server or editor")
             }
         }
 
-        Subcommands::Watch(_subargs) => match watch(&exercises, verbose, _subargs.success_hints) {
+        Subcommands::Watch(subargs) => match if subargs.tui {
+            tui::watch(&exercises, subargs.success_hints, subargs.poll, state)
+        } else {
+            watch(
+                &exercises,
+                verbose,
+                subargs.success_hints,
+                subargs.poll,
+                state,
+            )
+        } {
             Err(e) => {
-                println!(
+                reporter().info(&format!(
                     "Error: Could not watch your progress. Error message was {:?}.",
                     e
-                );
-                println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
+                ));
                 std::process::exit(1);
             }
             Ok(WatchStatus::Finished) => {
-                println!(
+                reporter().info(&format!(
                     "{emoji} All exercises completed! {emoji}",
                     emoji = Emoji("🎉", "★")
-                );
-                println!("\n{FENISH_LINE}\n");
+                ));
+                reporter().info(&format!("\n{FENISH_LINE}\n"));
             }
             Ok(WatchStatus::Unfinished) => {
-                println!("We hope you're enjoying learning about Rust!");
-                println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
+                reporter().info("We hope you're enjoying learning about Rust!");
+                reporter().info("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
             }
         },
     }
 }
 
-fn spawn_watch_shell(
-    failed_exercise_hint: &Arc<Mutex<Option<String>>>,
-    should_quit: Arc<AtomicBool>,
-) {
-    let failed_exercise_hint = Arc::clone(failed_exercise_hint);
-    println!("Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.");
-    thread::spawn(move || loop {
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => {
-                let input = input.trim();
-                if input == "hint" {
-                    if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                        println!("{hint}");
-                    }
-                } else if input == "clear" {
-                    println!("\x1B[2J\x1B[1;1H");
-                } else if input.eq("quit") {
-                    should_quit.store(true, Ordering::SeqCst);
-                    println!("Bye!");
-                } else if input.eq("help") {
-                    println!("Commands available to you in watch mode:");
-                    println!("  hint   - prints the current exercise's hint");
-                    println!("  clear  - clears the screen");
-                    println!("  quit   - quits watch mode");
-                    println!("  !<cmd> - executes a command, like `!rustc --explain E0381`");
-                    println!("  help   - displays this help message");
-                    println!();
-                    println!("Watch mode automatically re-evaluates the current exercise");
-                    println!("when you edit a file's contents.")
-                } else if let Some(cmd) = input.strip_prefix('!') {
-                    let parts: Vec<&str> = cmd.split_whitespace().collect();
-                    if parts.is_empty() {
-                        println!("no command provided");
-                    } else if let Err(e) = Command::new(parts[0]).args(&parts[1..]).status() {
-                        println!("failed to execute command `{}`: {}", cmd, e);
-                    }
-                } else {
-                    println!("unknown command: {input}");
-                }
-            }
-            Err(error) => println!("error reading command: {error}"),
+// Parse the command line like `argh::from_env`, but accept `--option=value`
+// as well, like most other tools do
+fn args_from_env() -> Args {
+    let strings: Vec<String> = std::env::args().collect();
+    let command = Path::new(&strings[0])
+        .file_name()
+        .and_then(OsStr::to_str)
+        .unwrap_or("rustlings");
+    let mut split = Vec::new();
+    for (i, arg) in strings[1..].iter().enumerate() {
+        if arg == "--" {
+            split.extend(strings[i + 1..].iter().map(String::as_str));
+            break;
         }
-    });
+        match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => split.extend([option, value]),
+            _ => split.push(arg),
+        }
+    }
+    Args::from_args(&[command], &split).unwrap_or_else(|early_exit| {
+        std::process::exit(match early_exit.status {
+            Ok(()) => {
+                println!("{}", early_exit.output);
+                0
+            }
+            Err(()) => {
+                eprintln!(
+                    "{}\nRun {command} --help for more information.",
+                    early_exit.output
+                );
+                1
+            }
+        })
+    })
 }
 
-fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
+fn find_exercise<'a>(name: &str, exercises: &'a [Exercise], state: &ProgressState) -> &'a Exercise {
     if name.eq("next") {
         exercises
             .iter()
-            .find(|e| !e.looks_done())
+            .find(|e| !state.is_done(e))
             .unwrap_or_else(|| {
-                println!("🎉 Congratulations! You have done all the exercises!");
-                println!("🔚 There are no more exercises to do next!");
+                if reporter::use_emoji() {
+                    reporter().info("🎉 Congratulations! You have done all the exercises!");
+                    reporter().info("🔚 There are no more exercises to do next!");
+                } else {
+                    reporter().info("Congratulations! You have done all the exercises!");
+                    reporter().info("There are no more exercises to do next!");
+                }
                 std::process::exit(1)
             })
     } else {
@@ -436,7 +626,7 @@ fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
             .iter()
             .find(|e| e.name == name)
             .unwrap_or_else(|| {
-                println!("No exercise found for '{name}'!");
+                reporter().info(&format!("No exercise found for '{name}'!"));
                 std::process::exit(1)
             })
     }
@@ -451,80 +641,143 @@ fn watch(
     exercises: &[Exercise],
     verbose: bool,
     success_hints: bool,
+    poll: bool,
+    state: ProgressState,
 ) -> notify::Result<WatchStatus> {
-    /* Clears the terminal with an ANSI escape code.
-    Works in UNIX and newer Windows terminals. */
-    fn clear_screen() {
-        println!("\x1Bc");
-    }
-
     let (tx, rx) = channel();
-    let should_quit = Arc::new(AtomicBool::new(false));
 
-    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(1))?;
-    watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+    let _watcher = watcher::watch_exercises(tx, poll);
 
-    clear_screen();
+    reporter().clear_screen();
 
-    let to_owned_hint = |t: &Exercise| t.hint.to_owned();
-    let failed_exercise_hint = match verify(
-        exercises.iter(),
-        (0, exercises.len()),
-        verbose,
-        success_hints,
-    ) {
-        Ok(_) => return Ok(WatchStatus::Finished),
-        Err(exercise) => Arc::new(Mutex::new(Some(to_owned_hint(exercise)))),
+    let shared = shell::Shared {
+        state: Arc::new(Mutex::new(state)),
+        ..shell::Shared::default()
     };
-    spawn_watch_shell(&failed_exercise_hint, Arc::clone(&should_quit));
-    loop {
-        match rx.recv_timeout(Duration::from_secs(1)) {
-            Ok(event) => match event {
-                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b) => {
-                    if b.extension() == Some(OsStr::new("rs")) && b.exists() {
+    let shared = &shared;
+    let status = thread::scope(|scope| {
+        // Verifying happens on a thread of its own, so that saving a file
+        // while it's still busy can cancel it and start over. Each one gets
+        // a token of its own, so cancelling it leaves the shell's commands be.
+        let verify_from = |changed: Option<PathBuf>| {
+            let cancel = CancelToken::default();
+            let token = cancel.clone();
+            let handle = scope.spawn(move || {
+                verify_pending(exercises, changed, verbose, success_hints, shared, &token)
+            });
+            (cancel, handle)
+        };
+        let mut verification = Some(verify_from(None));
+        let mut shell_started = false;
+        loop {
+            match rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(event) => match event {
+                    DebouncedEvent::Create(b)
+                    | DebouncedEvent::Chmod(b)
+                    | DebouncedEvent::Write(b)
+                        if b.extension() == Some(OsStr::new("rs")) && b.exists() =>
+                    {
                         let filepath = b.as_path().canonicalize().unwrap();
-                        let pending_exercises = exercises
-                            .iter()
-                            .find(|e| filepath.ends_with(&e.path))
-                            .into_iter()
-                            .chain(
-                                exercises
-                                    .iter()
-                                    .filter(|e| !e.looks_done() && !filepath.ends_with(&e.path)),
-                            );
-                        let num_done = exercises.iter().filter(|e| e.looks_done()).count();
-                        clear_screen();
-                        match verify(
-                            pending_exercises,
-                            (num_done, exercises.len()),
-                            verbose,
-                            success_hints,
-                        ) {
-                            Ok(_) => return Ok(WatchStatus::Finished),
-                            Err(exercise) => {
-                                let mut failed_exercise_hint = failed_exercise_hint.lock().unwrap();
-                                *failed_exercise_hint = Some(to_owned_hint(exercise));
-                            }
+                        if let Some((cancel, running)) = verification.take() {
+                            cancel.cancel();
+                            let _ignored = running.join();
                         }
+                        reporter().clear_screen();
+                        verification = Some(verify_from(Some(filepath)));
                     }
+                    _ => {}
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    // the timeout expired, just check the verification and
+                    // the `should_quit` variable below then loop again
                 }
-                _ => {}
-            },
-            Err(RecvTimeoutError::Timeout) => {
-                // the timeout expired, just check the `should_quit` variable below then loop again
+                Err(e) => reporter().info(&format!("watch error: {e:?}")),
+            }
+            let finished = match verification.take() {
+                Some((_, running)) if running.is_finished() => Some(running),
+                unfinished => {
+                    verification = unfinished;
+                    None
+                }
+            };
+            if let Some(finished) = finished {
+                match finished.join().expect("Verifying the exercises failed") {
+                    Ok(_) => return WatchStatus::Finished,
+                    Err(exercise) => *shared.current.lock().unwrap() = Some(exercise.clone()),
+                }
+                // The shell only starts once there is something to work on
+                if !shell_started {
+                    shell::spawn(exercises.to_vec(), shared.clone());
+                    shell_started = true;
+                }
+            }
+            // Check if we need to exit
+            if shared.should_quit.load(Ordering::SeqCst) {
+                if let Some((cancel, _)) = &verification {
+                    cancel.cancel();
+                }
+                return WatchStatus::Unfinished;
             }
-            Err(e) => println!("watch error: {e:?}"),
-        }
-        // Check if we need to exit
-        if should_quit.load(Ordering::SeqCst) {
-            return Ok(WatchStatus::Unfinished);
         }
-    }
+    });
+    // The shell may be in the middle of reading a line
+    shell::restore_terminal();
+    Ok(status)
+}
+
+// Verify the exercises that aren't done yet, starting with the one in the file
+// that changed and leaving skipped ones for last. Without a changed file, all
+// exercises are verified in order.
+// The state is only locked to pick the exercises, the shell may need it while
+// they compile.
+fn verify_pending<'a>(
+    exercises: &'a [Exercise],
+    changed: Option<PathBuf>,
+    verbose: bool,
+    success_hints: bool,
+    shared: &shell::Shared,
+    cancel: &CancelToken,
+) -> Result<(), &'a Exercise> {
+    let Some(filepath) = changed else {
+        return verify(
+            exercises,
+            (0, exercises.len()),
+            verbose,
+            success_hints,
+            &shared.state,
+            cancel,
+        );
+    };
+    let state = shared.state.lock().unwrap();
+    let skipped = shared.skipped.lock().unwrap().clone();
+    // Collected up front, as verifying updates the state.
+    // Skipped exercises come last.
+    let mut pending_exercises: Vec<&Exercise> = exercises
+        .iter()
+        .filter(|e| !state.is_done(e) && !filepath.ends_with(&e.path))
+        .collect();
+    pending_exercises.sort_by_key(|e| skipped.contains(&e.name));
+    let pending_exercises: Vec<&Exercise> = exercises
+        .iter()
+        .find(|e| filepath.ends_with(&e.path))
+        .into_iter()
+        .chain(pending_exercises)
+        .collect();
+    let num_done = exercises.iter().filter(|e| state.is_done(e)).count();
+    drop(state);
+    verify(
+        pending_exercises,
+        (num_done, exercises.len()),
+        verbose,
+        success_hints,
+        &shared.state,
+        cancel,
+    )
 }
 
 fn rustc_exists() -> bool {
     Command::new("rustc")
-        .args(&["--version"])
+        .args(["--version"])
         .stdout(Stdio::null())
         .spawn()
         .and_then(|mut child| child.wait())
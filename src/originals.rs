@@ -0,0 +1,84 @@
+// Pristine copies of the exercises, so they can be reset without git.
+//
+// The exercises are embedded into the binary when rustlings is built (see
+// build.rs). They are taken from the snapshot in `.rustlings-originals`, which
+// students don't edit, rather than from `exercises`, so rebuilding rustlings
+// in a checkout with solved exercises still embeds the original ones.
+// Resetting an exercise restores them, after moving the student's current
+// version to `.rustlings/backups`, from where `rustlings reset --undo` brings
+// it back.
+
+use crate::exercise::Exercise;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const BACKUPS_DIR: &str = ".rustlings/backups";
+// Where the originals are embedded from, see build.rs
+pub const SNAPSHOT_DIR: &str = ".rustlings-originals";
+
+include!(concat!(env!("OUT_DIR"), "/originals.rs"));
+
+// Restore the original of the exercise, keeping a backup of the current version
+pub fn reset(exercise: &Exercise) -> io::Result<()> {
+    let files = exercise
+        .source_files()
+        .into_iter()
+        .map(|file| embedded(&file).map(|original| (file, original)))
+        .collect::<io::Result<Vec<_>>>()?;
+    for (file, original) in files {
+        copy(&file, &Path::new(BACKUPS_DIR).join(&file))?;
+        fs::write(file, original)?;
+    }
+    Ok(())
+}
+
+// The original source of the exercise
+pub fn original(exercise: &Exercise) -> io::Result<String> {
+    embedded(&exercise.path).map(String::from)
+}
+
+// Bring back the version of the exercise from before it was last reset
+pub fn undo_reset(exercise: &Exercise) -> io::Result<()> {
+    let backups: Vec<(PathBuf, PathBuf)> = exercise
+        .source_files()
+        .into_iter()
+        .map(|file| (Path::new(BACKUPS_DIR).join(&file), file))
+        .filter(|(backup, _)| backup.exists())
+        .collect();
+    if backups.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{exercise} wasn't reset before"),
+        ));
+    }
+    for (backup, file) in backups {
+        copy(&backup, &file)?;
+        fs::remove_file(backup)?;
+    }
+    Ok(())
+}
+
+fn copy(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(from, to).map(|_| ())
+}
+
+// The original of the file, as it was when rustlings was built
+fn embedded(file: &Path) -> io::Result<&'static str> {
+    ORIGINALS
+        .iter()
+        .find(|(path, _)| Path::new(path) == file)
+        .map(|(_, original)| *original)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "{} isn't one of the exercises rustlings was built with",
+                    file.display()
+                ),
+            )
+        })
+}
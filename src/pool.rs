@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::thread;
+
+// The number of jobs to use when none was requested explicitly
+pub fn default_jobs() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+// Run `work` on every item using at most `jobs` threads at a time.
+// The results are handed to `on_result` on the calling thread, in the
+// order of `items`, no matter in which order the workers finish them.
+// This keeps everything printed from `on_result` independent of the concurrency.
+pub fn run_ordered<T, R, W, F>(items: &[T], jobs: usize, work: W, mut on_result: F)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    F: FnMut(usize, R),
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if tx.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        // Results that arrived before all of their predecessors
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (index, result) in rx {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&expected) {
+                on_result(expected, result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_results_in_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut seen = Vec::new();
+        run_ordered(
+            &items,
+            4,
+            |&i| {
+                // Make the early items finish last
+                thread::sleep(Duration::from_millis(20 - i));
+                i * 2
+            },
+            |index, result| seen.push((index, result)),
+        );
+        let expected: Vec<(usize, u64)> = items.iter().map(|&i| (i as usize, i * 2)).collect();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn test_no_items() {
+        let items: Vec<u32> = Vec::new();
+        let mut called = false;
+        run_ordered(&items, 8, |&i| i, |_, _| called = true);
+        assert!(!called);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::reporter::reporter;
 use glob::glob;
 use serde::{Deserialize, Serialize};
 use std::env;
@@ -84,9 +85,9 @@ impl RustAnalyzerProject {
 
         let toolchain = whitespace_iter.next().unwrap_or(&toolchain);
 
-        println!("Determined toolchain: {}\n", &toolchain);
+        reporter().info(&format!("Determined toolchain: {}\n", &toolchain));
 
-        self.sysroot_src = (std::path::Path::new(&*toolchain)
+        self.sysroot_src = (std::path::Path::new(toolchain)
             .join("lib")
             .join("rustlib")
             .join("src")
@@ -0,0 +1,313 @@
+// Everything the subcommands tell the user goes through a `Reporter`, chosen
+// once with the global `--format` flag:
+//
+// - `human`: colors, emoji and spinners, for a terminal
+// - `plain`: no ANSI codes, no emoji and no spinners, which suits screen
+//   readers and logs. It's the default when `NO_COLOR` is set.
+// - `json`: one JSON object per line, for scripts and editors
+//
+// Watch mode's shell goes through it as well. While `watch --tui` is on the
+// screen, messages are captured instead and shown in its status line.
+
+use console::{style, Emoji};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::json;
+use std::env;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Human,
+    Plain,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" => Ok(Format::Human),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format `{s}`, expected human, plain or json"
+            )),
+        }
+    }
+}
+
+impl Format {
+    // The format to use without `--format`
+    pub fn from_env() -> Format {
+        match env::var("NO_COLOR") {
+            Ok(value) if !value.is_empty() => Format::Plain,
+            _ => Format::Human,
+        }
+    }
+}
+
+pub trait Reporter: Sync {
+    // Something worked out, e.g. an exercise passed
+    fn success(&self, message: &str);
+    // Something went wrong, e.g. an exercise failed
+    fn warn(&self, message: &str);
+    // Any other line (or lines) of text for the user
+    fn info(&self, text: &str);
+    // What the compiler, the tests or an exercise printed
+    fn output(&self, text: &str);
+    // A spinner to show while `message` is going on
+    fn spinner(&self, message: String) -> ProgressBar;
+    // A bar showing how many of the exercises are done
+    fn progress(&self, done: usize, total: usize) -> ProgressBar;
+    // Start over on an empty screen, e.g. when watch mode verifies again
+    fn clear_screen(&self);
+}
+
+static FORMAT: OnceLock<Format> = OnceLock::new();
+
+// Choose the reporter. Only the first call has an effect.
+pub fn set_format(format: Format) {
+    if FORMAT.set(format).is_ok() && format != Format::Human {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+}
+
+pub fn format() -> Format {
+    FORMAT.get().copied().unwrap_or(Format::Human)
+}
+
+pub fn reporter() -> &'static dyn Reporter {
+    if CAPTURED.lock().unwrap().is_some() {
+        return &Captured;
+    }
+    match format() {
+        Format::Human => &Human,
+        Format::Plain => &Plain,
+        Format::Json => &Json,
+    }
+}
+
+// The messages kept while capturing, None when they are written out
+static CAPTURED: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+// Keep the messages from now on instead of writing them to stdout
+pub fn start_capturing() {
+    CAPTURED.lock().unwrap().get_or_insert_with(Vec::new);
+}
+
+// The messages kept since the last call
+pub fn take_captured() -> Vec<String> {
+    CAPTURED
+        .lock()
+        .unwrap()
+        .as_mut()
+        .map(std::mem::take)
+        .unwrap_or_default()
+}
+
+// Write the messages to stdout again, returning the ones that weren't taken
+pub fn stop_capturing() -> Vec<String> {
+    CAPTURED.lock().unwrap().take().unwrap_or_default()
+}
+
+// Whether emoji may be used, which only humans get unless `NO_EMOJI` is set
+pub fn use_emoji() -> bool {
+    format() == Format::Human && env::var("NO_EMOJI").is_err()
+}
+
+struct Human;
+
+impl Reporter for Human {
+    fn success(&self, message: &str) {
+        let mark = if use_emoji() {
+            Emoji("✅", "✓").to_string()
+        } else {
+            String::from("✓")
+        };
+        emit(&format!(
+            "{} {}",
+            style(mark).green(),
+            style(message).green()
+        ));
+    }
+
+    fn warn(&self, message: &str) {
+        let mark = if use_emoji() {
+            Emoji("⚠️ ", "!").to_string()
+        } else {
+            String::from("!")
+        };
+        emit(&format!("{} {}", style(mark).red(), style(message).red()));
+    }
+
+    fn info(&self, text: &str) {
+        emit(text);
+    }
+
+    fn output(&self, text: &str) {
+        emit(text);
+    }
+
+    fn spinner(&self, message: String) -> ProgressBar {
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_message(message);
+        spinner.enable_steady_tick(100);
+        spinner
+    }
+
+    fn progress(&self, done: usize, total: usize) -> ProgressBar {
+        let bar = ProgressBar::new(total as u64);
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
+                .progress_chars("#>-"),
+        );
+        bar.set_position(done as u64);
+        bar
+    }
+
+    fn clear_screen(&self) {
+        // Works in UNIX and newer Windows terminals
+        emit("\x1Bc");
+    }
+}
+
+struct Plain;
+
+impl Reporter for Plain {
+    fn success(&self, message: &str) {
+        emit(&format!("Success: {message}"));
+    }
+
+    fn warn(&self, message: &str) {
+        emit(&format!("Warning: {message}"));
+    }
+
+    fn info(&self, text: &str) {
+        emit(&console::strip_ansi_codes(text));
+    }
+
+    fn output(&self, text: &str) {
+        emit(&console::strip_ansi_codes(text));
+    }
+
+    // Said once instead of spinning
+    fn spinner(&self, message: String) -> ProgressBar {
+        emit(&message);
+        ProgressBar::hidden()
+    }
+
+    fn progress(&self, done: usize, total: usize) -> ProgressBar {
+        emit(&format!("Progress: {done}/{total} exercises done"));
+        ProgressBar::hidden()
+    }
+
+    fn clear_screen(&self) {}
+}
+
+struct Json;
+
+impl Reporter for Json {
+    fn success(&self, message: &str) {
+        emit(&json!({ "event": "success", "message": message }).to_string());
+    }
+
+    fn warn(&self, message: &str) {
+        emit(&json!({ "event": "warning", "message": message }).to_string());
+    }
+
+    fn info(&self, text: &str) {
+        let text = console::strip_ansi_codes(text);
+        emit(&json!({ "event": "message", "text": text }).to_string());
+    }
+
+    fn output(&self, text: &str) {
+        let text = console::strip_ansi_codes(text);
+        emit(&json!({ "event": "output", "text": text }).to_string());
+    }
+
+    fn spinner(&self, _message: String) -> ProgressBar {
+        ProgressBar::hidden()
+    }
+
+    fn progress(&self, done: usize, total: usize) -> ProgressBar {
+        emit(&json!({ "event": "progress", "done": done, "total": total }).to_string());
+        ProgressBar::hidden()
+    }
+
+    fn clear_screen(&self) {}
+}
+
+// Keeps each message as a single line, for a status line
+struct Captured;
+
+impl Captured {
+    fn keep(&self, text: &str) {
+        let text = console::strip_ansi_codes(text);
+        let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some(captured) = CAPTURED.lock().unwrap().as_mut() {
+            captured.push(line);
+        }
+    }
+}
+
+impl Reporter for Captured {
+    fn success(&self, message: &str) {
+        self.keep(message);
+    }
+
+    fn warn(&self, message: &str) {
+        self.keep(message);
+    }
+
+    fn info(&self, text: &str) {
+        self.keep(text);
+    }
+
+    fn output(&self, text: &str) {
+        self.keep(text);
+    }
+
+    fn spinner(&self, _message: String) -> ProgressBar {
+        ProgressBar::hidden()
+    }
+
+    fn progress(&self, _done: usize, _total: usize) -> ProgressBar {
+        ProgressBar::hidden()
+    }
+
+    fn clear_screen(&self) {}
+}
+
+// Write a JSON event of its own, like the ones of `verify --message-format=json`
+pub fn event(event: &serde_json::Value) {
+    emit(&event.to_string());
+}
+
+// Write a line to stdout. `println!` panics once stdout is closed, e.g. when
+// it's piped into `head`, so that ends the program quietly instead.
+fn emit(line: &str) {
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = writeln!(stdout, "{line}") {
+        match e.kind() {
+            io::ErrorKind::BrokenPipe => std::process::exit(0),
+            _ => std::process::exit(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("JSON".parse(), Ok(Format::Json));
+        assert_eq!("plain".parse(), Ok(Format::Plain));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
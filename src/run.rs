@@ -1,54 +1,102 @@
-use std::process::Command;
-
 use crate::exercise::{Exercise, Mode};
-use crate::verify::test;
-use indicatif::ProgressBar;
+use crate::originals;
+use crate::reporter::reporter;
+use crate::state::ProgressState;
+use crate::verify::{print_error_hints, test, warn_compile_timed_out, warn_timed_out};
+use console::style;
+use std::sync::Mutex;
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
 // The verbose argument helps determine whether or not to show
 // the output from the test harnesses (if the mode of the exercise is test)
-pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
+// A successful run is recorded in the progress state.
+pub fn run(exercise: &Exercise, verbose: bool, state: &Mutex<ProgressState>) -> Result<(), ()> {
     match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
-        Mode::Compile => compile_and_run(exercise)?,
-        Mode::Clippy => compile_and_run(exercise)?,
-        Mode::BuildScript => test(exercise, verbose)?,
+        Mode::Test => test(exercise, verbose, state)?,
+        Mode::Compile => compile_and_run(exercise, state)?,
+        Mode::Clippy => compile_and_run(exercise, state)?,
+        Mode::BuildScript => test(exercise, verbose, state)?,
     }
     Ok(())
 }
 
-// Resets the exercise by stashing the changes.
-pub fn reset(exercise: &Exercise) -> Result<(), ()> {
-    let command = Command::new("git")
-        .args(["stash", "--"])
-        .arg(&exercise.path)
-        .spawn();
+// Resets the exercise to its original version.
+// The current version is backed up, so that `undo` can bring it back.
+pub fn reset(exercise: &Exercise, undo: bool) -> Result<(), ()> {
+    let result = if undo {
+        originals::undo_reset(exercise)
+    } else {
+        originals::reset(exercise)
+    };
 
-    match command {
-        Ok(_) => Ok(()),
-        Err(_) => Err(()),
+    match (result, undo) {
+        (Ok(()), false) => {
+            success!(
+                "Reset {}, `rustlings reset --undo {}` brings your version back",
+                exercise,
+                exercise.name
+            );
+            Ok(())
+        }
+        (Ok(()), true) => {
+            success!("Brought back your version of {}", exercise);
+            Ok(())
+        }
+        (Err(e), _) => {
+            warn!("Failed to reset {}: {}", exercise, e);
+            Err(())
+        }
     }
 }
 
+// Print the hints of the exercise revealed so far, after revealing the next
+// one (or all of them). Returns whether there are any hints left to reveal.
+pub fn hint(exercise: &Exercise, state: &mut ProgressState, all: bool) -> bool {
+    let levels = exercise.hint.levels();
+    let revealed = state.reveal_hints(exercise, all);
+    // A single hint is printed just like it always was
+    if let [hint] = levels {
+        reporter().info(hint);
+        return false;
+    }
+    for (i, hint) in levels[..revealed].iter().enumerate() {
+        if i > 0 {
+            reporter().info("");
+        }
+        reporter().info(
+            &style(format!("Hint {}/{}:", i + 1, levels.len()))
+                .bold()
+                .to_string(),
+        );
+        reporter().info(hint);
+    }
+    revealed < levels.len()
+}
+
 // Invoke the rust compiler on the path of the given exercise
 // and run the ensuing binary.
 // This is strictly for non-test binaries, so output is displayed
-fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+fn compile_and_run(exercise: &Exercise, state: &Mutex<ProgressState>) -> Result<(), ()> {
+    let progress_bar = reporter().spinner(format!("Compiling {exercise}..."));
 
     let compilation_result = exercise.compile();
     let compilation = match compilation_result {
         Ok(compilation) => compilation,
+        Err(output) if output.timed_out => {
+            progress_bar.finish_and_clear();
+            warn_compile_timed_out(exercise);
+            reporter().output(&output.stderr);
+            return Err(());
+        }
         Err(output) => {
             progress_bar.finish_and_clear();
             warn!(
                 "Compilation of {} failed!, Compiler error message:\n",
                 exercise
             );
-            println!("{}", output.stderr);
+            reporter().output(&output.stderr);
+            print_error_hints(exercise, &output);
             return Err(());
         }
     };
@@ -59,15 +107,23 @@ fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
 
     match result {
         Ok(output) => {
-            println!("{}", output.stdout);
+            reporter().output(&output.stdout);
             success!("Successfully ran {}", exercise);
+            // Running a clippy exercise doesn't check its lints, so that's no pass yet
+            if let Mode::Compile = exercise.mode {
+                state.lock().unwrap().record_pass(exercise);
+            }
             Ok(())
         }
         Err(output) => {
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
+            reporter().output(&output.stdout);
+            reporter().output(&output.stderr);
 
-            warn!("Ran {} with errors", exercise);
+            if output.timed_out {
+                warn_timed_out(exercise);
+            } else {
+                warn!("Ran {} with errors", exercise);
+            }
             Err(())
         }
     }
@@ -0,0 +1,382 @@
+// Running student code in a sandbox on Linux.
+//
+// The exercise is started in new user, mount, network and IPC namespaces:
+// there's no network, every mount is made read-only apart from a scratch
+// directory, and the number of processes is limited. All of this works
+// without privileges, but not every system allows unprivileged namespaces
+// (e.g. some containers). There the exercise simply runs without a sandbox.
+// Directories that exercises mustn't see at all, like hidden tests, are
+// covered by an empty one.
+//
+// Either way, the exercise only gets a few harmless environment variables,
+// so secrets like the key reports are signed with never reach it.
+
+use crate::reporter::reporter;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+static HIDDEN: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+// The environment variables that are passed on to exercises: enough for
+// cargo, rustup and the tests to work, and nothing that could hold a secret
+const ALLOWED_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LANG",
+    "LC_ALL",
+    "TERM",
+    "TMPDIR",
+    "CARGO_HOME",
+    "RUSTUP_HOME",
+    "RUSTUP_TOOLCHAIN",
+    "RUST_BACKTRACE",
+    "SYSTEMROOT",
+];
+
+// Run exercises without a sandbox, even if it's available
+pub fn disable() {
+    DISABLED.store(true, Ordering::SeqCst);
+}
+
+// Keep the directory out of sight of everything that runs in a sandbox
+pub fn hide(dir: &Path) {
+    if let Ok(dir) = dir.canonicalize() {
+        HIDDEN.lock().unwrap().push(dir);
+    }
+}
+
+// Whether exercises run in a sandbox
+pub fn enabled() -> bool {
+    !DISABLED.load(Ordering::SeqCst) && linux::available()
+}
+
+// Tell the user if exercises are going to run without a sandbox on Linux,
+// because the system doesn't allow one. This is done once from `main`, as
+// the sandbox is first needed in the middle of e.g. the TUI.
+pub fn warn_if_unavailable() {
+    if cfg!(target_os = "linux") && !enabled() && !DISABLED.load(Ordering::SeqCst) {
+        reporter().warn("Sandboxing isn't supported on this system, exercises run without one.");
+    }
+}
+
+// Confine the command to a sandbox in which only `scratch` is writable.
+// The environment is cleared apart from `ALLOWED_ENV` in any case, the rest
+// does nothing if sandboxing was disabled or isn't supported.
+pub fn confine(cmd: &mut Command, scratch: &Path) {
+    cmd.env_clear();
+    for (name, value) in env::vars_os() {
+        if name
+            .to_str()
+            .is_some_and(|name| ALLOWED_ENV.contains(&name))
+        {
+            cmd.env(name, value);
+        }
+    }
+    if enabled() {
+        linux::confine(cmd, scratch, &HIDDEN.lock().unwrap());
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::env;
+    use std::ffi::{CStr, CString};
+    use std::fs;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::process::CommandExt;
+    use std::path::{Path, PathBuf};
+    use std::process::{Command, Stdio};
+    use std::ptr;
+    use std::sync::OnceLock;
+
+    // The most processes (and threads) a sandboxed exercise may have.
+    // The kernel doesn't enforce this for root.
+    const MAX_PROCESSES: u64 = 256;
+
+    // Whether sandboxes can be created on this system.
+    // This is found out once by running `rustlings --help` in one.
+    pub fn available() -> bool {
+        static AVAILABLE: OnceLock<bool> = OnceLock::new();
+        *AVAILABLE.get_or_init(|| {
+            let Ok(exe) = env::current_exe() else {
+                return false;
+            };
+            let mut probe = Command::new(exe);
+            probe
+                .arg("--help")
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null());
+            confine(&mut probe, &env::temp_dir(), &[]);
+            probe.status().map(|s| s.success()).unwrap_or(false)
+        })
+    }
+
+    // Everything the child needs, prepared up front: between fork and exec
+    // only async-signal-safe functions may be called, so nothing can be
+    // allocated there.
+    struct Setup {
+        scratch: CString,
+        mount_points: Vec<CString>,
+        hidden: Vec<CString>,
+        setgroups: CString,
+        uid_map_path: CString,
+        uid_map: Vec<u8>,
+        gid_map_path: CString,
+        gid_map: Vec<u8>,
+    }
+
+    pub fn confine(cmd: &mut Command, scratch: &Path, hidden: &[PathBuf]) {
+        // SAFETY: getuid and getgid can't fail
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        let setup = Setup {
+            scratch: c_path(scratch),
+            mount_points: mount_points(),
+            hidden: hidden.iter().map(|dir| c_path(dir)).collect(),
+            setgroups: c_path(Path::new("/proc/self/setgroups")),
+            uid_map_path: c_path(Path::new("/proc/self/uid_map")),
+            uid_map: format!("{uid} {uid} 1").into_bytes(),
+            gid_map_path: c_path(Path::new("/proc/self/gid_map")),
+            gid_map: format!("{gid} {gid} 1").into_bytes(),
+        };
+        cmd.env("TMPDIR", scratch);
+        // SAFETY: `enter` only calls async-signal-safe functions
+        unsafe {
+            cmd.pre_exec(move || enter(&setup));
+        }
+    }
+
+    fn enter(setup: &Setup) -> io::Result<()> {
+        // SAFETY: all pointers point to valid, NUL terminated strings or are null where allowed
+        unsafe {
+            check(libc::unshare(
+                libc::CLONE_NEWUSER | libc::CLONE_NEWNS | libc::CLONE_NEWNET | libc::CLONE_NEWIPC,
+            ))?;
+            map_ids(setup)?;
+
+            // Don't let any of the following leak out of the namespace
+            check(libc::mount(
+                ptr::null(),
+                c"/".as_ptr(),
+                ptr::null(),
+                libc::MS_REC | libc::MS_PRIVATE,
+                ptr::null(),
+            ))?;
+            // Make the scratch directory a mount of its own, so it stays
+            // writable when the mount it lives on becomes read-only
+            check(libc::mount(
+                setup.scratch.as_ptr(),
+                setup.scratch.as_ptr(),
+                ptr::null(),
+                libc::MS_BIND | libc::MS_REC,
+                ptr::null(),
+            ))?;
+            // /proc is needed to enter the namespace below and comes last
+            let proc = c"/proc";
+            for mount_point in setup.mount_points.iter().filter(|m| m.as_c_str() != proc) {
+                let result = remount_read_only(mount_point);
+                // Some mounts can't be remounted (e.g. ones that are hidden by
+                // other mounts), but the root file system has to work
+                if mount_point.as_bytes() == b"/" {
+                    result?;
+                }
+            }
+            for dir in &setup.hidden {
+                check(libc::mount(
+                    c"none".as_ptr(),
+                    dir.as_ptr(),
+                    c"tmpfs".as_ptr(),
+                    libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                    ptr::null(),
+                ))?;
+            }
+            // Mounts can be undone by whoever owns the namespace they were
+            // made in. Entering yet another one locks them in place.
+            check(libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNS))?;
+            map_ids(setup)?;
+            let _ignored = remount_read_only(proc);
+
+            let limit = libc::rlimit {
+                rlim_cur: MAX_PROCESSES,
+                rlim_max: MAX_PROCESSES,
+            };
+            check(libc::setrlimit(libc::RLIMIT_NPROC, &limit))?;
+        }
+        Ok(())
+    }
+
+    // Map the user and group to themselves in a freshly entered user namespace
+    unsafe fn map_ids(setup: &Setup) -> io::Result<()> {
+        write_file(&setup.setgroups, b"deny")?;
+        write_file(&setup.uid_map_path, &setup.uid_map)?;
+        write_file(&setup.gid_map_path, &setup.gid_map)
+    }
+
+    unsafe fn remount_read_only(mount_point: &CStr) -> io::Result<()> {
+        // The flags that are already set have to be kept,
+        // the kernel doesn't allow dropping them in a user namespace
+        let mut stat: libc::statvfs = std::mem::zeroed();
+        check(libc::statvfs(mount_point.as_ptr(), &mut stat))?;
+        let mut flags = libc::MS_REMOUNT | libc::MS_BIND | libc::MS_RDONLY;
+        for (st, ms) in [
+            (libc::ST_NOSUID, libc::MS_NOSUID),
+            (libc::ST_NODEV, libc::MS_NODEV),
+            (libc::ST_NOEXEC, libc::MS_NOEXEC),
+            (libc::ST_NOATIME, libc::MS_NOATIME),
+            (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+            (libc::ST_RELATIME, libc::MS_RELATIME),
+        ] {
+            if stat.f_flag & st != 0 {
+                flags |= ms;
+            }
+        }
+        check(libc::mount(
+            ptr::null(),
+            mount_point.as_ptr(),
+            ptr::null(),
+            flags,
+            ptr::null(),
+        ))
+    }
+
+    unsafe fn write_file(path: &CString, contents: &[u8]) -> io::Result<()> {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        check(fd)?;
+        let written = libc::write(fd, contents.as_ptr().cast(), contents.len());
+        libc::close(fd);
+        if written == contents.len() as isize {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    fn check(result: libc::c_int) -> io::Result<()> {
+        if result < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn c_path(path: &Path) -> CString {
+        CString::new(path.as_os_str().as_bytes()).expect("Paths can't contain NUL bytes")
+    }
+
+    // All mount points of the current mount namespace
+    fn mount_points() -> Vec<CString> {
+        let mountinfo = fs::read_to_string("/proc/self/mountinfo").unwrap_or_default();
+        mountinfo
+            .lines()
+            .filter_map(|line| line.split(' ').nth(4))
+            .map(|mount_point| c_path(Path::new(&unescape(mount_point))))
+            .collect()
+    }
+
+    // Mount points in /proc/self/mountinfo have spaces etc. escaped as octal, e.g. `\040`
+    fn unescape(escaped: &str) -> String {
+        let mut bytes = Vec::with_capacity(escaped.len());
+        let mut rest = escaped.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            let octal = tail
+                .get(..3)
+                .and_then(|digits| std::str::from_utf8(digits).ok())
+                .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+            match (byte, octal) {
+                (b'\\', Some(value)) => {
+                    bytes.push(value);
+                    rest = &tail[3..];
+                }
+                _ => {
+                    bytes.push(byte);
+                    rest = tail;
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).to_string()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_only_scratch_is_writable() {
+            if !available() {
+                return;
+            }
+            let base = env::temp_dir().join(format!("rustlings_sandbox_{}", std::process::id()));
+            let (scratch, other) = (base.join("scratch"), base.join("other"));
+            fs::create_dir_all(&scratch).unwrap();
+            fs::create_dir_all(&other).unwrap();
+
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c")
+                .arg("touch \"$1/allowed\"; touch \"$2/denied\"")
+                .arg("sh")
+                .arg(&scratch)
+                .arg(&other)
+                .stderr(Stdio::null());
+            confine(&mut cmd, &scratch, &[]);
+            cmd.status().unwrap();
+
+            let (allowed, denied) = (
+                scratch.join("allowed").exists(),
+                other.join("denied").exists(),
+            );
+            fs::remove_dir_all(&base).unwrap();
+            assert!(allowed);
+            assert!(!denied);
+        }
+
+        #[test]
+        fn test_hidden_dirs_stay_hidden() {
+            if !available() {
+                return;
+            }
+            let base = env::temp_dir().join(format!("rustlings_hidden_{}", std::process::id()));
+            let (scratch, hidden) = (base.join("scratch"), base.join("hidden"));
+            fs::create_dir_all(&scratch).unwrap();
+            fs::create_dir_all(&hidden).unwrap();
+            fs::write(hidden.join("tests.rs"), "secret").unwrap();
+
+            // Taking the mount away again mustn't work either
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c")
+                .arg("umount \"$1\"; cat \"$1/tests.rs\"")
+                .arg("sh")
+                .arg(&hidden)
+                .stderr(Stdio::null());
+            confine(&mut cmd, &scratch, &[hidden]);
+            let output = cmd.output().unwrap();
+
+            fs::remove_dir_all(&base).unwrap();
+            assert!(!String::from_utf8_lossy(&output.stdout).contains("secret"));
+        }
+
+        #[test]
+        fn test_unescape() {
+            assert_eq!(unescape("/"), "/");
+            assert_eq!(unescape(r"/mnt/my\040disk"), "/mnt/my disk");
+            assert_eq!(unescape(r"/a\134b"), r"/a\b");
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod linux {
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    pub fn available() -> bool {
+        false
+    }
+
+    pub fn confine(_cmd: &mut Command, _scratch: &Path, _hidden: &[PathBuf]) {}
+}
@@ -0,0 +1,540 @@
+// The command shell of watch mode.
+//
+// Lines are read with line editing and a history that is kept in
+// `.rustlings/history`, and split into words the way a Unix shell does, so
+// arguments can be quoted. Most commands work on the current exercise, the
+// one watch mode stopped at, which `run` and `skip` change.
+
+use crate::exercise::Exercise;
+use crate::limits::CancelToken;
+use crate::originals;
+use crate::reporter::reporter;
+use crate::run::{hint, reset, run};
+use crate::state::ProgressState;
+use crate::verify::verify;
+use console::style;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::collections::BTreeSet;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+const HISTORY_PATH: &str = ".rustlings/history";
+
+// What watch mode and its shell share
+#[derive(Clone, Default)]
+pub struct Shared {
+    // The exercise watch mode stopped at
+    pub current: Arc<Mutex<Option<Exercise>>>,
+    pub state: Arc<Mutex<ProgressState>>,
+    // The names of the exercises that were skipped, which are verified last
+    pub skipped: Arc<Mutex<BTreeSet<String>>>,
+    pub should_quit: Arc<AtomicBool>,
+}
+
+#[derive(PartialEq, Debug)]
+enum ShellCommand {
+    Hint,
+    Run(Option<String>),
+    Reset,
+    List,
+    Skip,
+    Diff,
+    Clear,
+    Quit,
+    Help,
+    Exec(Vec<String>),
+}
+
+pub fn spawn(exercises: Vec<Exercise>, shared: Shared) {
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(e) => {
+            warn!("The watch mode shell isn't available: {}", e);
+            return;
+        }
+    };
+    let _ignored = editor.load_history(HISTORY_PATH);
+    terminal::save_mode();
+    reporter().info(
+        "Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.",
+    );
+    thread::spawn(move || loop {
+        let line = match editor.readline("") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                shared.should_quit.store(true, Ordering::SeqCst);
+                reporter().info("Bye!");
+                break;
+            }
+            // There's nothing left to read, but watch mode goes on
+            Err(ReadlineError::Eof) => break,
+            Err(error) => {
+                warn!("error reading command: {}", error);
+                break;
+            }
+        };
+        if !line.trim().is_empty() {
+            let _ignored = editor.add_history_entry(line.as_str());
+            let _ignored = editor.save_history(HISTORY_PATH);
+        }
+        match parse(&line) {
+            Ok(Some(ShellCommand::Quit)) => {
+                shared.should_quit.store(true, Ordering::SeqCst);
+                reporter().info("Bye!");
+                break;
+            }
+            Ok(Some(command)) => execute(command, &exercises, &shared),
+            Ok(None) => {}
+            Err(error) => reporter().warn(&error),
+        }
+    });
+}
+
+// Put the terminal back the way it was before the shell read from it
+pub fn restore_terminal() {
+    terminal::restore_mode();
+}
+
+// Parse a line of input, or None if it's empty
+fn parse(line: &str) -> Result<Option<ShellCommand>, String> {
+    if let Some(command) = line.trim_start().strip_prefix('!') {
+        let words = split_words(command)?;
+        if words.is_empty() {
+            return Err(String::from("no command provided"));
+        }
+        return Ok(Some(ShellCommand::Exec(words)));
+    }
+    let words = split_words(line)?;
+    let Some((name, args)) = words.split_first() else {
+        return Ok(None);
+    };
+    let command = match (name.as_str(), args) {
+        ("hint", []) => ShellCommand::Hint,
+        ("run", []) => ShellCommand::Run(None),
+        ("run", [exercise]) => ShellCommand::Run(Some(exercise.clone())),
+        ("reset", []) => ShellCommand::Reset,
+        ("list", []) => ShellCommand::List,
+        ("skip", []) => ShellCommand::Skip,
+        ("diff", []) => ShellCommand::Diff,
+        ("clear", []) => ShellCommand::Clear,
+        ("quit", []) => ShellCommand::Quit,
+        ("help", []) => ShellCommand::Help,
+        ("hint" | "reset" | "list" | "skip" | "diff" | "clear" | "quit" | "help", _) => {
+            return Err(format!("{name} doesn't take any arguments"))
+        }
+        ("run", _) => return Err(String::from("run takes at most one exercise")),
+        _ => return Err(format!("unknown command: {}", line.trim())),
+    };
+    Ok(Some(command))
+}
+
+fn execute(command: ShellCommand, exercises: &[Exercise], shared: &Shared) {
+    let current = shared.current.lock().unwrap().clone();
+    let needs_current = matches!(
+        command,
+        ShellCommand::Hint
+            | ShellCommand::Run(None)
+            | ShellCommand::Reset
+            | ShellCommand::Skip
+            | ShellCommand::Diff
+    );
+    if needs_current && current.is_none() {
+        reporter().warn("There is no current exercise");
+        return;
+    }
+    match command {
+        ShellCommand::Hint => {
+            let exercise = current.unwrap();
+            if hint(&exercise, &mut shared.state.lock().unwrap(), false) {
+                reporter().info("\nType 'hint' again for the next hint.");
+            }
+        }
+        ShellCommand::Run(name) => {
+            let state = shared.state.lock().unwrap();
+            let exercise = match name.as_deref() {
+                None => current,
+                Some("next") => exercises.iter().find(|e| !state.is_done(e)).cloned(),
+                Some(name) => exercises.iter().find(|e| e.name == name).cloned(),
+            };
+            // Verifying in the background needs the state while this compiles
+            drop(state);
+            let Some(exercise) = exercise else {
+                warn!("No exercise found for '{}'!", name.unwrap_or_default());
+                return;
+            };
+            let _ignored = run(&exercise, false, &shared.state);
+            *shared.current.lock().unwrap() = Some(exercise);
+        }
+        ShellCommand::Reset => {
+            let _ignored = reset(&current.unwrap(), false);
+        }
+        ShellCommand::List => list(exercises, current.as_ref(), &shared.state.lock().unwrap()),
+        ShellCommand::Skip => skip(&current.unwrap(), exercises, shared),
+        ShellCommand::Diff => diff(&current.unwrap()),
+        ShellCommand::Clear => reporter().clear_screen(),
+        ShellCommand::Quit => {}
+        ShellCommand::Help => {
+            reporter().info(
+                "Commands available to you in watch mode:
+  hint       - prints the current exercise's hints, revealing the next one
+  run [name] - runs the current exercise, or jumps to the one with the given name
+  reset      - resets the current exercise to its original version
+  list       - lists all exercises and whether they are done
+  skip       - leaves the current exercise for later and moves on
+  diff       - shows your changes to the current exercise
+  clear      - clears the screen
+  quit       - quits watch mode
+  !<cmd>     - executes a command, like `!rustc --explain E0381`
+  help       - displays this help message
+
+Watch mode automatically re-evaluates the current exercise
+when you edit a file's contents.",
+            );
+        }
+        ShellCommand::Exec(words) => {
+            if let Err(e) = Command::new(&words[0]).args(&words[1..]).status() {
+                warn!("failed to execute command `{}`: {}", words.join(" "), e);
+            }
+        }
+    }
+}
+
+fn list(exercises: &[Exercise], current: Option<&Exercise>, state: &ProgressState) {
+    let mut lines = Vec::new();
+    let mut done = 0;
+    for exercise in exercises {
+        let status = if state.is_done(exercise) {
+            done += 1;
+            "Done"
+        } else {
+            "Pending"
+        };
+        let marker = if current.is_some_and(|c| c.name == exercise.name) {
+            "*"
+        } else {
+            " "
+        };
+        lines.push(format!("{marker} {:<17}\t{status}", exercise.name));
+    }
+    lines.push(format!(
+        "Progress: You completed {} / {} exercises ({:.1} %).",
+        done,
+        exercises.len(),
+        done as f32 / exercises.len().max(1) as f32 * 100.0
+    ));
+    reporter().info(&lines.join("\n"));
+}
+
+// Leave the exercise for later and move on to the next pending one
+fn skip(exercise: &Exercise, exercises: &[Exercise], shared: &Shared) {
+    let state = shared.state.lock().unwrap();
+    let mut skipped = shared.skipped.lock().unwrap();
+    skipped.insert(exercise.name.clone());
+    let position = exercises.iter().position(|e| e.name == exercise.name);
+    let start = position.map_or(0, |i| i + 1);
+    let next = exercises[start..]
+        .iter()
+        .chain(&exercises[..start])
+        .filter(|e| !state.is_done(e) && e.name != exercise.name)
+        .min_by_key(|e| skipped.contains(&e.name));
+    let Some(next) = next else {
+        reporter().warn("There is no other pending exercise to move on to");
+        return;
+    };
+    reporter().info(&format!("Skipped {exercise} for now, on to {next}!"));
+    let num_done = exercises.iter().filter(|e| state.is_done(e)).count();
+    let next = next.clone();
+    drop(skipped);
+    drop(state);
+    let _ignored = verify(
+        [&next],
+        (num_done, exercises.len()),
+        false,
+        false,
+        &shared.state,
+        &CancelToken::default(),
+    )
+    .is_ok();
+    *shared.current.lock().unwrap() = Some(next);
+}
+
+// Print the changes to the exercise since it was first seen
+fn diff(exercise: &Exercise) {
+    let (original, current) = match (
+        originals::original(exercise),
+        std::fs::read_to_string(&exercise.path),
+    ) {
+        (Ok(original), Ok(current)) => (original, current),
+        (Err(e), _) | (_, Err(e)) => {
+            warn!("Failed to compare {} with its original: {}", exercise, e);
+            return;
+        }
+    };
+    let changes = diff_lines(&original, &current);
+    if changes
+        .iter()
+        .all(|change| matches!(change, Change::Same(_)))
+    {
+        reporter().info(&format!("You haven't changed {exercise} yet"));
+        return;
+    }
+    let mut lines = Vec::new();
+    for hunk in hunks(&changes, 2) {
+        lines.push(
+            style(format!("@@ line {} @@", hunk.first_line))
+                .cyan()
+                .to_string(),
+        );
+        for change in hunk.changes {
+            lines.push(match change {
+                Change::Same(line) => format!("  {line}"),
+                Change::Removed(line) => style(format!("- {line}")).red().to_string(),
+                Change::Added(line) => style(format!("+ {line}")).green().to_string(),
+            });
+        }
+    }
+    reporter().output(&lines.join("\n"));
+}
+
+#[derive(PartialEq, Debug)]
+enum Change<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// Consecutive changes with some unchanged lines around them
+struct Hunk<'a, 'b> {
+    // The line of the new version the hunk starts at
+    first_line: usize,
+    changes: &'b [Change<'a>],
+}
+
+// The changes that turn `old` into `new`, from their longest common subsequence
+// of lines. Exercises are small enough for the quadratic table.
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<Change<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut changes = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            changes.push(Change::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            changes.push(Change::Removed(old[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Added(new[j]));
+            j += 1;
+        }
+    }
+    changes
+}
+
+// Group the changes into hunks, with `context` unchanged lines around them
+fn hunks<'a, 'b>(changes: &'b [Change<'a>], context: usize) -> Vec<Hunk<'a, 'b>> {
+    let is_change = |i: usize| !matches!(changes[i], Change::Same(_));
+    let mut hunks = Vec::new();
+    let mut i = 0;
+    while i < changes.len() {
+        if !is_change(i) {
+            i += 1;
+            continue;
+        }
+        let start = i.saturating_sub(context);
+        let mut end = i;
+        // Extend the hunk as long as the next change is close enough
+        while end < changes.len()
+            && (end..(end + 2 * context + 1).min(changes.len())).any(is_change)
+        {
+            end += 1;
+        }
+        let end = (end + context).min(changes.len());
+        let first_line = 1 + changes[..start]
+            .iter()
+            .filter(|change| !matches!(change, Change::Removed(_)))
+            .count();
+        hunks.push(Hunk {
+            first_line,
+            changes: &changes[start..end],
+        });
+        i = end;
+    }
+    hunks
+}
+
+// Split a line into words like a POSIX shell does: whitespace separates words
+// unless it's quoted, single quotes keep everything as it is, and a backslash
+// takes the next character literally (within double quotes only before `"`
+// and another backslash).
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    // Whether there is a word, which may be empty like ''
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(String::from("missing closing single quote")),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(String::from("missing closing double quote")),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(String::from("missing closing double quote")),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => {
+                    in_word = true;
+                    word.push(c);
+                }
+                None => return Err(String::from("nothing to escape after the backslash")),
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+// rustyline puts the terminal into raw mode while it waits for a line.
+// As watch mode may end during that, the mode from before is restored on exit.
+#[cfg(unix)]
+mod terminal {
+    use std::sync::OnceLock;
+
+    static MODE: OnceLock<libc::termios> = OnceLock::new();
+
+    pub fn save_mode() {
+        // SAFETY: termios is plain data, and the pointer is valid for the call
+        unsafe {
+            let mut mode: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut mode) == 0 {
+                let _ignored = MODE.set(mode);
+            }
+        }
+    }
+
+    pub fn restore_mode() {
+        if let Some(mode) = MODE.get() {
+            // SAFETY: the pointer is valid for the call
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, mode);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod terminal {
+    pub fn save_mode() {}
+
+    pub fn restore_mode() {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"  rustc --explain  E0381 "#).unwrap(),
+            ["rustc", "--explain", "E0381"]
+        );
+        assert_eq!(
+            split_words(r#"echo "hello world" 'it''s' a\ b "" "say \"hi\" \n""#).unwrap(),
+            ["echo", "hello world", "its", "a b", "", r#"say "hi" \n"#]
+        );
+        assert!(split_words("echo 'unterminated").is_err());
+        assert!(split_words("echo \\").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("   "), Ok(None));
+        assert_eq!(parse("run"), Ok(Some(ShellCommand::Run(None))));
+        assert_eq!(
+            parse("run 'variables1'"),
+            Ok(Some(ShellCommand::Run(Some(String::from("variables1")))))
+        );
+        assert_eq!(
+            parse(r#"!grep -n "I AM NOT DONE" exercises/intro/intro1.rs"#),
+            Ok(Some(ShellCommand::Exec(vec![
+                String::from("grep"),
+                String::from("-n"),
+                String::from("I AM NOT DONE"),
+                String::from("exercises/intro/intro1.rs"),
+            ])))
+        );
+        assert!(parse("skip now").is_err());
+        assert!(parse("frobnicate").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\n";
+        let changes = diff_lines(old, new);
+        assert_eq!(changes[1], Change::Removed("b"));
+        assert_eq!(changes[2], Change::Added("B"));
+        assert_eq!(changes.last(), Some(&Change::Added("i")));
+
+        let hunks = hunks(&changes, 2);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].first_line, 1);
+        assert_eq!(hunks[0].changes.len(), 5);
+        assert_eq!(hunks[1].first_line, 7);
+        assert_eq!(
+            hunks[1].changes,
+            [Change::Same("g"), Change::Same("h"), Change::Added("i")]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// The progress of the student, kept in `.rustlings/state.json`.
+//
+// Whether an exercise is done used to be decided by the `I AM NOT DONE`
+// comment alone, which can simply be deleted. Instead, every time an exercise
+// actually compiles and passes, the hash of its source is recorded here. It
+// counts as done as long as its source is still the one that passed and the
+// comment is gone. The old behaviour is still available as a legacy mode.
+//
+// `.rustlings` isn't committed, so how many hints were revealed is kept in
+// `.rustlings-hints.json` next to info.toml instead, which is: the grader only
+// sees the repository, and the report includes the hints of every exercise.
+// Nothing stops students from editing that file, so the counts are only as
+// trustworthy as the students are.
+
+use crate::digest::sha256_hex;
+use crate::exercise::Exercise;
+use crate::reporter::reporter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const STATE_PATH: &str = ".rustlings/state.json";
+pub const HINTS_PATH: &str = ".rustlings-hints.json";
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct ProgressState {
+    // The exercises that passed at some point, by name
+    #[serde(default)]
+    pub exercises: BTreeMap<String, ExerciseProgress>,
+    // The exercises whose bonus tests passed as well at some point, by name
+    #[serde(default)]
+    pub bonus_passed: BTreeMap<String, ExerciseProgress>,
+    // How many hints of each exercise were revealed, by name. Read from older
+    // state files, but saved to `HINTS_PATH`.
+    #[serde(default, skip_serializing)]
+    pub hints_revealed: BTreeMap<String, usize>,
+    // Whether only the `I AM NOT DONE` comment decides if an exercise is done
+    #[serde(skip)]
+    legacy_marker: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExerciseProgress {
+    // When the exercise last compiled and passed, in seconds since the Unix epoch
+    pub passed_at: u64,
+    // The hash of the exercise's source files at that time
+    pub source_hash: String,
+}
+
+impl ProgressState {
+    // Load the state of the current directory. A missing or broken state file
+    // just means that nothing has been verified yet.
+    pub fn load(legacy_marker: bool) -> ProgressState {
+        let mut state: ProgressState = fs::read_to_string(STATE_PATH)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        if let Some(hints_revealed) = fs::read_to_string(HINTS_PATH)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+        {
+            state.hints_revealed = hints_revealed;
+        }
+        state.legacy_marker = legacy_marker;
+        state
+    }
+
+    // Remember that the exercise just compiled and passed in its current state.
+    // The state is saved right away, as watch mode is usually left with Ctrl-C.
+    pub fn record_pass(&mut self, exercise: &Exercise) {
+        if let Some(progress) = ExerciseProgress::now(exercise) {
+            self.exercises.insert(exercise.name.clone(), progress);
+            self.save_or_warn();
+        }
+    }
+
+    // Remember that the exercise just passed including its bonus tests
+    pub fn record_bonus_pass(&mut self, exercise: &Exercise) {
+        if let Some(progress) = ExerciseProgress::now(exercise) {
+            self.bonus_passed.insert(exercise.name.clone(), progress);
+            self.save_or_warn();
+        }
+    }
+
+    // Reveal the next hint of the exercise (or all of them) and
+    // return how many of its hints are revealed now
+    pub fn reveal_hints(&mut self, exercise: &Exercise, all: bool) -> usize {
+        let revealed = self.reveal(exercise, all);
+        self.save_or_warn();
+        revealed
+    }
+
+    // Save the progress and the hints, saying which of them couldn't be saved
+    fn save_or_warn(&self) {
+        if let Err(e) = save_json(Path::new(STATE_PATH), self) {
+            reporter().info(&format!(
+                "Failed to save your progress to {STATE_PATH}: {e}"
+            ));
+        }
+        if self.hints_revealed.is_empty() {
+            return;
+        }
+        if let Err(e) = save_json(Path::new(HINTS_PATH), &self.hints_revealed) {
+            reporter().info(&format!(
+                "Failed to save the hints you revealed to {HINTS_PATH}: {e}"
+            ));
+        }
+    }
+
+    fn reveal(&mut self, exercise: &Exercise, all: bool) -> usize {
+        let levels = exercise.hint.levels().len();
+        let revealed = if all {
+            levels
+        } else {
+            (self.hints_revealed(exercise) + 1).min(levels)
+        };
+        self.hints_revealed.insert(exercise.name.clone(), revealed);
+        revealed
+    }
+
+    pub fn hints_revealed(&self, exercise: &Exercise) -> usize {
+        self.hints_revealed
+            .get(&exercise.name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // Whether the exercise is done: the `I AM NOT DONE` comment was removed
+    // and, unless in legacy mode, the source is the same that last passed.
+    pub fn is_done(&self, exercise: &Exercise) -> bool {
+        if !exercise.looks_done() {
+            return false;
+        }
+        if self.legacy_marker {
+            return true;
+        }
+        match self.exercises.get(&exercise.name) {
+            Some(progress) => source_hash(exercise).as_ref() == Some(&progress.source_hash),
+            None => false,
+        }
+    }
+}
+
+impl ExerciseProgress {
+    // The progress of an exercise that passes right now in its current state
+    fn now(exercise: &Exercise) -> Option<ExerciseProgress> {
+        let passed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Some(ExerciseProgress {
+            passed_at,
+            source_hash: source_hash(exercise)?,
+        })
+    }
+}
+
+// The hash of all of the exercise's source files
+fn source_hash(exercise: &Exercise) -> Option<String> {
+    let mut hashes = String::new();
+    for file in exercise.source_files() {
+        hashes += &sha256_hex(fs::read(file).ok()?);
+        hashes.push('\n');
+    }
+    Some(sha256_hex(hashes))
+}
+
+// Save the value as JSON. It's written to a temporary file first, so that an
+// interrupted write never loses what was saved before.
+fn save_json(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, serde_json::to_string_pretty(value)?)?;
+    fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::{Hint, Mode};
+
+    fn exercise(path: &str) -> Exercise {
+        Exercise::for_test("example", path, Mode::Compile)
+    }
+
+    fn passed(exercise: &Exercise) -> ProgressState {
+        let mut state = ProgressState::default();
+        state.exercises.insert(
+            exercise.name.clone(),
+            ExerciseProgress {
+                passed_at: 0,
+                source_hash: source_hash(exercise).unwrap(),
+            },
+        );
+        state
+    }
+
+    #[test]
+    fn test_done_needs_a_pass() {
+        let finished = exercise("tests/fixture/state/finished_exercise.rs");
+        assert!(!ProgressState::default().is_done(&finished));
+        assert!(passed(&finished).is_done(&finished));
+    }
+
+    #[test]
+    fn test_pass_is_tied_to_source() {
+        let finished = exercise("tests/fixture/state/finished_exercise.rs");
+        let mut state = passed(&finished);
+        state.exercises.get_mut("example").unwrap().source_hash = sha256_hex("changed");
+        assert!(!state.is_done(&finished));
+    }
+
+    #[test]
+    fn test_pass_with_marker_is_not_done() {
+        let pending = exercise("tests/fixture/state/pending_exercise.rs");
+        assert!(!passed(&pending).is_done(&pending));
+    }
+
+    #[test]
+    fn test_reveal_hints() {
+        let mut exercise = exercise("tests/fixture/state/finished_exercise.rs");
+        exercise.hint = Hint::Levels(vec![String::from("a"), String::from("b")]);
+        let mut state = ProgressState::default();
+        assert_eq!(state.hints_revealed(&exercise), 0);
+        assert_eq!(state.reveal(&exercise, false), 1);
+        assert_eq!(state.reveal(&exercise, false), 2);
+        assert_eq!(state.reveal(&exercise, false), 2);
+
+        let mut state = ProgressState::default();
+        assert_eq!(state.reveal(&exercise, true), 2);
+    }
+
+    #[test]
+    fn test_legacy_marker() {
+        let finished = exercise("tests/fixture/state/finished_exercise.rs");
+        let pending = exercise("tests/fixture/state/pending_exercise.rs");
+        let state = ProgressState {
+            legacy_marker: true,
+            ..ProgressState::default()
+        };
+        assert!(state.is_done(&finished));
+        assert!(!state.is_done(&pending));
+    }
+}
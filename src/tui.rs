@@ -0,0 +1,597 @@
+// A full-screen terminal UI for watch mode, started with `watch --tui`.
+//
+// The screen is split into the overall progress, the output of compiling or
+// testing the current exercise, the lines around its `I AM NOT DONE` comment
+// and the hints revealed so far. Everything else is done with single keys.
+// The terminal is put back into its normal state when leaving, also on
+// Ctrl-C (which arrives as a key press in raw mode) and on panics.
+
+use crate::cache::Outcome;
+use crate::exercise::{Exercise, ExerciseOutput, State};
+use crate::limits::CancelToken;
+use crate::originals;
+use crate::reporter::{self, reporter};
+use crate::state::ProgressState;
+use crate::verify;
+use crate::watcher;
+use crate::WatchStatus;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use notify::DebouncedEvent;
+use std::ffi::OsStr;
+use std::io::{self, Write};
+use std::sync::mpsc::channel;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const KEYS: &str = "h hint · n next · r reset · l list · ↑↓ scroll · q quit";
+
+pub fn watch(
+    exercises: &[Exercise],
+    success_hints: bool,
+    poll: bool,
+    state: ProgressState,
+) -> notify::Result<WatchStatus> {
+    let (tx, rx) = channel();
+    let _watcher = watcher::watch_exercises(tx, poll);
+
+    let _terminal = TerminalGuard::enter()?;
+    let mut app = App {
+        exercises,
+        state,
+        success_hints,
+        current: 0,
+        check: None,
+        verdict: Verdict::Passed,
+        output: Vec::new(),
+        scroll: 0,
+        show_list: false,
+        message: String::new(),
+    };
+    if app.verify(None)? {
+        return Ok(WatchStatus::Finished);
+    }
+    loop {
+        if event::poll(Duration::from_millis(100))? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if let Some(status) = app.handle_key(key)? {
+                        app.cancel();
+                        return Ok(status);
+                    }
+                }
+                Event::Resize(_, _) => app.draw()?,
+                _ => {}
+            }
+        }
+        while let Ok(event) = rx.try_recv() {
+            if let DebouncedEvent::Create(path)
+            | DebouncedEvent::Chmod(path)
+            | DebouncedEvent::Write(path) = event
+            {
+                if path.extension() != Some(OsStr::new("rs")) || !path.exists() {
+                    continue;
+                }
+                let path = path.canonicalize()?;
+                let changed = exercises.iter().position(|e| path.ends_with(&e.path));
+                if app.verify(changed)? {
+                    return Ok(WatchStatus::Finished);
+                }
+            }
+        }
+        if app.check.as_ref().is_some_and(|c| c.handle.is_finished()) && app.finish()? {
+            return Ok(WatchStatus::Finished);
+        }
+    }
+}
+
+// An exercise being verified on a thread of its own, so that saving a file,
+// moving on or quitting doesn't have to wait for it to compile
+struct Check {
+    index: usize,
+    // The exercises to verify next if this one is done
+    queue: Vec<usize>,
+    cancel: CancelToken,
+    handle: JoinHandle<Outcome>,
+}
+
+// Raw mode and the alternate screen, for as long as this is alive.
+// Messages of the reporter are captured meanwhile, so they don't end up
+// written over the screen, and the ones not shown yet are written after it.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        let guard = TerminalGuard;
+        reporter::start_capturing();
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ignored = execute!(io::stdout(), ResetColor, Show, LeaveAlternateScreen);
+        let _ignored = terminal::disable_raw_mode();
+        for message in reporter::stop_capturing() {
+            reporter().info(&message);
+        }
+    }
+}
+
+// What verifying the current exercise came to
+#[derive(PartialEq, Debug)]
+enum Verdict {
+    CompileError,
+    RunError,
+    TimedOut,
+    TestsChanged,
+    // It passes, but still has its `I AM NOT DONE` comment
+    Passed,
+}
+
+impl Verdict {
+    fn describe(&self) -> &'static str {
+        match self {
+            Verdict::CompileError => "doesn't compile",
+            Verdict::RunError => "fails",
+            Verdict::TimedOut => "ran for too long",
+            Verdict::TestsChanged => "has changed tests, press r to reset it",
+            Verdict::Passed => "passes! Remove `I AM NOT DONE` to move on",
+        }
+    }
+}
+
+struct App<'a> {
+    exercises: &'a [Exercise],
+    state: ProgressState,
+    success_hints: bool,
+    // The exercise that is shown, the first one that isn't done yet
+    current: usize,
+    // The exercise that is being verified right now, if any
+    check: Option<Check>,
+    verdict: Verdict,
+    // The output of compiling or running the current exercise
+    output: Vec<String>,
+    // How many lines of the output (or the list) are scrolled past
+    scroll: usize,
+    // Whether the list of exercises is shown instead of the output
+    show_list: bool,
+    // What the last action came to and what the reporter was told since,
+    // shown instead of the keys
+    message: String,
+}
+
+impl<'a> App<'a> {
+    // Verify the changed exercise (if any) and then the pending ones,
+    // starting from the current one, until one of them isn't done.
+    // Whatever was being verified before is cancelled.
+    // Returns whether all exercises are done.
+    fn verify(&mut self, changed: Option<usize>) -> io::Result<bool> {
+        self.cancel();
+        let total = self.exercises.len();
+        let queue = changed.into_iter().chain(
+            (0..total)
+                .map(|i| (self.current + i) % total)
+                .filter(|&i| Some(i) != changed && !self.state.is_done(&self.exercises[i])),
+        );
+        self.start(queue.collect())
+    }
+
+    // Start verifying the first exercise of the queue.
+    // Returns whether the queue is empty, so all exercises are done.
+    fn start(&mut self, mut queue: Vec<usize>) -> io::Result<bool> {
+        if queue.is_empty() {
+            return Ok(true);
+        }
+        let index = queue.remove(0);
+        let exercise = self.exercises[index].clone();
+        if exercise.tests_changed() {
+            let message = format!(
+                "The tests of {exercise} were changed! Please solve it without changing the tests."
+            );
+            self.show(index, Verdict::TestsChanged, vec![message])?;
+            return Ok(false);
+        }
+        let cancel = CancelToken::default();
+        let token = cancel.clone();
+        let handle = thread::spawn(move || verify::check(&exercise, &token));
+        self.check = Some(Check {
+            index,
+            queue,
+            cancel,
+            handle,
+        });
+        self.draw()?;
+        Ok(false)
+    }
+
+    // Take the outcome of the finished check: show why its exercise isn't
+    // done yet, or move on to the next one in the queue.
+    // Returns whether all exercises are done.
+    fn finish(&mut self) -> io::Result<bool> {
+        let Some(check) = self.check.take() else {
+            return Ok(false);
+        };
+        let exercise = &self.exercises[check.index];
+        let outcome = check.handle.join().expect("Verifying the exercise failed");
+        let (verdict, text) = match outcome {
+            Outcome::CompileError(output) if output.timed_out => {
+                (Verdict::TimedOut, text_of(&output))
+            }
+            Outcome::CompileError(output) => (Verdict::CompileError, output.stderr),
+            Outcome::RunError(output) if output.timed_out => (Verdict::TimedOut, text_of(&output)),
+            Outcome::RunError(output) => (Verdict::RunError, text_of(&output)),
+            // Whatever cancelled it has started over already
+            Outcome::Cancelled => return Ok(false),
+            Outcome::Success(output) => {
+                self.state.record_pass(exercise);
+                if exercise.looks_done() {
+                    return self.start(check.queue);
+                }
+                (Verdict::Passed, text_of(&output))
+            }
+        };
+        self.show(check.index, verdict, lines_of(&text))?;
+        Ok(false)
+    }
+
+    // Stop verifying, when it has to start over or watch mode ends
+    fn cancel(&mut self) {
+        if let Some(check) = self.check.take() {
+            check.cancel.cancel();
+            let _ignored = check.handle.join();
+        }
+    }
+
+    // Make the exercise the current one, with why it isn't done yet
+    fn show(&mut self, index: usize, verdict: Verdict, output: Vec<String>) -> io::Result<()> {
+        if self.current != index {
+            self.scroll = 0;
+        }
+        self.current = index;
+        self.verdict = verdict;
+        self.output = output;
+        self.draw()
+    }
+
+    // Handle a key press, returning how watch mode ended if it did
+    fn handle_key(&mut self, key: KeyEvent) -> io::Result<Option<WatchStatus>> {
+        let exercises = self.exercises;
+        let exercise = &exercises[self.current];
+        self.message.clear();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(WatchStatus::Unfinished)),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(Some(WatchStatus::Unfinished))
+            }
+            KeyCode::Char('h') => {
+                let levels = exercise.hint.levels().len();
+                let before = self.state.hints_revealed(exercise);
+                let revealed = self.state.reveal_hints(exercise, false);
+                self.message = if revealed == before {
+                    String::from("All hints are revealed already")
+                } else if revealed < levels {
+                    String::from("Press h again for the next hint")
+                } else {
+                    String::new()
+                };
+            }
+            KeyCode::Char('n') => {
+                let total = self.exercises.len();
+                let next = (1..total)
+                    .map(|i| (self.current + i) % total)
+                    .find(|&i| !self.state.is_done(&self.exercises[i]));
+                match next {
+                    Some(next) => {
+                        self.current = next;
+                        self.scroll = 0;
+                        self.show_list = false;
+                        return Ok(self.verify(None)?.then_some(WatchStatus::Finished));
+                    }
+                    None => self.message = String::from("There is no other pending exercise"),
+                }
+            }
+            KeyCode::Char('r') => {
+                self.message = match originals::reset(exercise) {
+                    Ok(()) => format!(
+                        "Reset {exercise}, `rustlings reset --undo {}` brings your version back",
+                        exercise.name
+                    ),
+                    Err(e) => format!("Failed to reset {exercise}: {e}"),
+                };
+                return Ok(self
+                    .verify(Some(self.current))?
+                    .then_some(WatchStatus::Finished));
+            }
+            KeyCode::Char('l') => {
+                self.show_list = !self.show_list;
+                self.scroll = if self.show_list {
+                    self.current.saturating_sub(2)
+                } else {
+                    0
+                };
+            }
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll += 1,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll += 10,
+            _ => return Ok(None),
+        }
+        self.draw()?;
+        Ok(None)
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        for captured in reporter::take_captured() {
+            if !self.message.is_empty() {
+                self.message += " · ";
+            }
+            self.message += &captured;
+        }
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        let exercises = self.exercises;
+        let exercise = &exercises[self.current];
+        let mut out = io::stdout().lock();
+        queue!(out, Clear(ClearType::All))?;
+
+        // Header and progress
+        let (title, color) = match &self.check {
+            Some(check) => (
+                format!("Checking {}...", exercises[check.index]),
+                Color::Yellow,
+            ),
+            None if self.verdict == Verdict::Passed => (
+                format!("{exercise} {}", self.verdict.describe()),
+                Color::Green,
+            ),
+            None => (
+                format!("{exercise} {}", self.verdict.describe()),
+                Color::Red,
+            ),
+        };
+        queue!(
+            out,
+            MoveTo(0, 0),
+            SetForegroundColor(color),
+            SetAttribute(Attribute::Bold),
+            Print(fit(&title, width)),
+            SetAttribute(Attribute::Reset),
+            ResetColor,
+        )?;
+        let done = self
+            .exercises
+            .iter()
+            .filter(|e| self.state.is_done(e))
+            .count();
+        queue!(
+            out,
+            MoveTo(0, 1),
+            Print(fit(&progress_bar(done, self.exercises.len(), width), width))
+        )?;
+
+        // The panes below the progress, from the bottom up
+        let context: Vec<(String, bool)> = match exercise.state() {
+            State::Pending(context) => context
+                .into_iter()
+                .map(|c| (format!("{:>3} | {}", c.number, c.line), c.important))
+                .collect(),
+            State::Done => Vec::new(),
+        };
+        let hints: Vec<(String, bool)> = self
+            .hint_text(exercise)
+            .iter()
+            .flat_map(|text| wrap(text, width))
+            .map(|line| (line, false))
+            .collect();
+        let footer_row = height.saturating_sub(1);
+        let hint_height = if hints.is_empty() {
+            0
+        } else {
+            (hints.len() + 1).min(height / 3)
+        };
+        let context_height = if context.is_empty() {
+            0
+        } else {
+            context.len() + 1
+        };
+        let hint_top = footer_row.saturating_sub(hint_height);
+        let context_top = hint_top.saturating_sub(context_height);
+        let main_height = context_top.saturating_sub(2);
+
+        let main: Vec<(String, bool)> = if self.show_list {
+            self.list_lines()
+        } else {
+            self.output
+                .iter()
+                .flat_map(|line| wrap(line, width))
+                .map(|line| (line, false))
+                .collect()
+        };
+        self.scroll = self
+            .scroll
+            .min(main.len().saturating_sub(main_height.saturating_sub(1)));
+        let main_title = if self.show_list {
+            "Exercises"
+        } else {
+            "Output"
+        };
+        draw_pane(
+            &mut out,
+            main_title,
+            &main[self.scroll..],
+            2,
+            main_height,
+            width,
+        )?;
+        draw_pane(
+            &mut out,
+            "Exercise",
+            &context,
+            context_top,
+            context_height,
+            width,
+        )?;
+        draw_pane(&mut out, "Hint", &hints, hint_top, hint_height, width)?;
+
+        let footer = if self.message.is_empty() {
+            KEYS
+        } else {
+            &self.message
+        };
+        queue!(
+            out,
+            MoveTo(0, footer_row as u16),
+            SetAttribute(Attribute::Reverse),
+            Print(format!("{:<width$}", fit(footer, width))),
+            SetAttribute(Attribute::Reset),
+        )?;
+        out.flush()
+    }
+
+    // The hints of the exercise revealed so far, or all of them if
+    // it passes and `--success-hints` was given
+    fn hint_text(&self, exercise: &Exercise) -> Vec<String> {
+        let levels = exercise.hint.levels();
+        let revealed = if self.success_hints && self.verdict == Verdict::Passed {
+            levels.len()
+        } else {
+            self.state.hints_revealed(exercise).min(levels.len())
+        };
+        if levels.len() == 1 {
+            return levels[..revealed].to_vec();
+        }
+        levels[..revealed]
+            .iter()
+            .enumerate()
+            .map(|(i, hint)| format!("Hint {}/{}:\n{hint}", i + 1, levels.len()))
+            .collect()
+    }
+
+    // All exercises with whether they are done, the current one highlighted
+    fn list_lines(&self) -> Vec<(String, bool)> {
+        self.exercises
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let status = if self.state.is_done(e) {
+                    "Done"
+                } else {
+                    "Pending"
+                };
+                let line = format!("{:<17}\t{}\t{status}", e.name, e.path.display());
+                (line.replace('\t', "  "), i == self.current)
+            })
+            .collect()
+    }
+}
+
+// Draw a pane with a title line and as many of the lines as fit.
+// Lines marked as important are drawn in bold.
+fn draw_pane(
+    out: &mut impl Write,
+    title: &str,
+    lines: &[(String, bool)],
+    top: usize,
+    height: usize,
+    width: usize,
+) -> io::Result<()> {
+    if height == 0 {
+        return Ok(());
+    }
+    let title = format!("── {title} {}", "─".repeat(width));
+    queue!(
+        out,
+        MoveTo(0, top as u16),
+        SetForegroundColor(Color::Blue),
+        Print(fit(&title, width)),
+        ResetColor
+    )?;
+    for (row, (line, important)) in lines.iter().take(height - 1).enumerate() {
+        queue!(out, MoveTo(0, (top + 1 + row) as u16))?;
+        if *important {
+            queue!(out, SetAttribute(Attribute::Bold))?;
+        }
+        queue!(out, Print(fit(line, width)), SetAttribute(Attribute::Reset))?;
+    }
+    Ok(())
+}
+
+// What a test harness or a program printed
+fn text_of(output: &ExerciseOutput) -> String {
+    format!("{}{}", output.stdout, output.stderr)
+}
+
+// The lines of the output without colors and tabs, which would throw off wrapping
+fn lines_of(text: &str) -> Vec<String> {
+    console::strip_ansi_codes(text)
+        .replace('\t', "    ")
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+// Break a line into pieces of at most `width` characters
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.is_empty() || width == 0 {
+        return vec![String::new()];
+    }
+    chars
+        .chunks(width)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+// Cut a line down to `width` characters
+fn fit(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let percentage = done as f32 / total.max(1) as f32 * 100.0;
+    let label = format!(" {done}/{total} ({percentage:.1} %)");
+    let bar_width = width
+        .saturating_sub(label.len() + "Progress: []".len())
+        .min(60);
+    let filled = done * bar_width / total.max(1);
+    let arrow = usize::from(filled < bar_width && done < total);
+    format!(
+        "Progress: [{}{}{}]{label}",
+        "#".repeat(filled),
+        ">".repeat(arrow),
+        "-".repeat(bar_width - filled - arrow)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(progress_bar(1, 4, 33), "Progress: [##>-----] 1/4 (25.0 %)");
+        assert_eq!(progress_bar(4, 4, 33), "Progress: [#######] 4/4 (100.0 %)");
+        assert_eq!(progress_bar(0, 0, 10), "Progress: [] 0/0 (0.0 %)");
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("abcdefg", 3), vec!["abc", "def", "g"]);
+        assert_eq!(wrap("", 3), vec![""]);
+        assert_eq!(fit("abcdefg", 3), "abc");
+    }
+
+    #[test]
+    fn test_lines_of() {
+        assert_eq!(
+            lines_of("\u{1b}[31merror\u{1b}[0m:\tx\nnext"),
+            vec!["error:    x", "next"]
+        );
+    }
+}
@@ -1,33 +1,12 @@
+// Report something that went wrong or worked out, see reporter.rs
 macro_rules! warn {
-    ($fmt:literal, $ex:expr) => {{
-        use console::{style, Emoji};
-        use std::env;
-        let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
-            println!("{} {}", style("!").red(), style(formatstr).red());
-        } else {
-            println!(
-                "{} {}",
-                style(Emoji("⚠️ ", "!")).red(),
-                style(formatstr).red()
-            );
-        }
+    ($fmt:literal, $($ex:expr),+) => {{
+        $crate::reporter::reporter().warn(&format!($fmt, $($ex),+));
     }};
 }
 
 macro_rules! success {
-    ($fmt:literal, $ex:expr) => {{
-        use console::{style, Emoji};
-        use std::env;
-        let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
-            println!("{} {}", style("✓").green(), style(formatstr).green());
-        } else {
-            println!(
-                "{} {}",
-                style(Emoji("✅", "✓")).green(),
-                style(formatstr).green()
-            );
-        }
+    ($fmt:literal, $($ex:expr),+) => {{
+        $crate::reporter::reporter().success(&format!($fmt, $($ex),+));
     }};
 }
@@ -1,36 +1,56 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
+use crate::cache::{self, Outcome};
+use crate::cicv::test_counts;
+use crate::diagnostics::Diagnostic;
+use crate::exercise::{failed_tests, Exercise, ExerciseOutput, Mode, State};
+use crate::limits::{self, CancelToken};
+use crate::reporter::{self, reporter};
+use crate::state::ProgressState;
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::env;
+use indicatif::ProgressBar;
+use serde_json::{json, Value};
+use std::sync::Mutex;
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
 // Any such failures will be reported to the end user.
 // If the Exercise being verified is a test, the verbose boolean
 // determines whether or not the test harness outputs are displayed.
+// Every exercise that passes is recorded in the progress state, which is
+// only locked for that, so it can be shared with watch mode's shell.
+// Cancelling the token stops verifying at the exercise at hand.
 pub fn verify<'a>(
     exercises: impl IntoIterator<Item = &'a Exercise>,
     progress: (usize, usize),
     verbose: bool,
     success_hints: bool,
+    state: &Mutex<ProgressState>,
+    cancel: &CancelToken,
 ) -> Result<(), &'a Exercise> {
     let (num_done, total) = progress;
-    let bar = ProgressBar::new(total as u64);
+    let bar = reporter().progress(num_done, total);
     let mut percentage = num_done as f32 / total as f32 * 100.0;
-    bar.set_style(ProgressStyle::default_bar()
-        .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
-        .progress_chars("#>-")
-    );
-    bar.set_position(num_done as u64);
     bar.set_message(format!("({:.1} %)", percentage));
 
     for exercise in exercises {
         let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
-            Mode::Compile => compile_and_run_interactively(exercise, success_hints),
-            Mode::Clippy => compile_only(exercise, success_hints),
-            Mode::BuildScript => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
-
+            Mode::Test => compile_and_test(
+                exercise,
+                RunMode::Interactive,
+                verbose,
+                success_hints,
+                state,
+                cancel,
+            ),
+            Mode::Compile => compile_and_run_interactively(exercise, success_hints, state, cancel),
+            Mode::Clippy => compile_only(exercise, success_hints, state, cancel),
+            Mode::BuildScript => compile_and_test(
+                exercise,
+                RunMode::Interactive,
+                verbose,
+                success_hints,
+                state,
+                cancel,
+            ),
         };
         if !compile_result.unwrap_or(false) {
             return Err(exercise);
@@ -42,69 +62,283 @@ pub fn verify<'a>(
     Ok(())
 }
 
+// Verify the exercises like `verify` does, stopping at the first one that
+// isn't done, but report what happens as JSON events, one per line, for
+// editors and CI dashboards:
+//
+// - `exercise-started`: the exercise is about to be compiled
+// - `compile-finished`: whether it compiled, and the compiler's diagnostics
+// - `test-results`: the counts and the failed tests, for exercises with tests
+// - `exercise-passed`: it passed, `marker` says where the `I AM NOT DONE`
+//   comment is if it's still there
+// - `exercise-failed`: it didn't pass, `reason` says why
+// - `progress`: how many of the exercises are done, at the very end
+pub fn verify_events<'a>(
+    exercises: &'a [Exercise],
+    state: &mut ProgressState,
+) -> Result<(), &'a Exercise> {
+    let result = match exercises.iter().find(|e| !exercise_events(e, state)) {
+        Some(exercise) => Err(exercise),
+        None => Ok(()),
+    };
+    let done = exercises.iter().filter(|e| state.is_done(e)).count();
+    reporter::event(&json!({ "event": "progress", "done": done, "total": exercises.len() }));
+    result
+}
+
+// Report on a single exercise, returns whether it's done
+fn exercise_events(exercise: &Exercise, state: &mut ProgressState) -> bool {
+    exercise_event(
+        "exercise-started",
+        exercise,
+        json!({ "mode": exercise.mode }),
+    );
+    if exercise.tests_changed() {
+        exercise_event(
+            "exercise-failed",
+            exercise,
+            json!({ "reason": "tests-changed" }),
+        );
+        return false;
+    }
+
+    let outcome = check(exercise, &CancelToken::default());
+    let diagnostics: Vec<Diagnostic> = match &outcome {
+        Outcome::CompileError(output) => output
+            .diagnostics
+            .iter()
+            .map(|diagnostic| Diagnostic {
+                rendered: plain(&diagnostic.rendered),
+                ..diagnostic.clone()
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let compiled = !matches!(outcome, Outcome::CompileError(_) | Outcome::Cancelled);
+    exercise_event(
+        "compile-finished",
+        exercise,
+        json!({ "success": compiled, "diagnostics": diagnostics }),
+    );
+
+    if let (Mode::Test | Mode::BuildScript, Outcome::Success(output) | Outcome::RunError(output)) =
+        (exercise.mode, &outcome)
+    {
+        exercise_event(
+            "test-results",
+            exercise,
+            json!({
+                "counts": test_counts(&output.stdout),
+                "failed_tests": failed_tests(&output.stdout),
+            }),
+        );
+    }
+
+    let output = match outcome {
+        Outcome::Success(output) => output,
+        Outcome::CompileError(output) => {
+            let reason = if output.timed_out {
+                "timed-out"
+            } else {
+                "compile-error"
+            };
+            let fields = json!({ "reason": reason, "stderr": plain(&output.stderr) });
+            exercise_event("exercise-failed", exercise, fields);
+            return false;
+        }
+        Outcome::RunError(output) => {
+            let reason = match exercise.mode {
+                _ if output.timed_out => "timed-out",
+                Mode::Test | Mode::BuildScript => "test-failure",
+                Mode::Compile | Mode::Clippy => "run-error",
+            };
+            let fields = json!({
+                "reason": reason,
+                "stdout": plain(&output.stdout),
+                "stderr": plain(&output.stderr),
+            });
+            exercise_event("exercise-failed", exercise, fields);
+            return false;
+        }
+        Outcome::Cancelled => {
+            exercise_event(
+                "exercise-failed",
+                exercise,
+                json!({ "reason": "cancelled" }),
+            );
+            return false;
+        }
+    };
+
+    state.record_pass(exercise);
+    let marker = match exercise.state() {
+        State::Done => None,
+        State::Pending(context) => context
+            .into_iter()
+            .find(|line| line.important)
+            .map(|line| json!({ "line": line.number, "column": line.marker_column })),
+    };
+    let done = marker.is_none();
+    exercise_event(
+        "exercise-passed",
+        exercise,
+        json!({ "done": done, "marker": marker, "stdout": plain(&output.stdout) }),
+    );
+    done
+}
+
+// The text without the colors the compiler and the tests add
+fn plain(text: &str) -> String {
+    console::strip_ansi_codes(text).to_string()
+}
+
+// An event about the exercise, with the given fields added to it
+fn exercise_event(event: &str, exercise: &Exercise, fields: Value) {
+    let mut event = json!({ "event": event, "exercise": exercise.name, "path": exercise.path });
+    if let (Some(event), Value::Object(fields)) = (event.as_object_mut(), fields) {
+        event.extend(fields);
+    }
+    reporter::event(&event);
+}
+
 enum RunMode {
     Interactive,
     NonInteractive,
+    // Like NonInteractive, but the bonus tests are run as well
+    Bonus,
 }
 
 // Compile and run the resulting test harness of the given Exercise
-pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose, false)?;
+pub fn test(exercise: &Exercise, verbose: bool, state: &Mutex<ProgressState>) -> Result<(), ()> {
+    compile_and_test(
+        exercise,
+        RunMode::NonInteractive,
+        verbose,
+        false,
+        state,
+        &CancelToken::default(),
+    )?;
+    Ok(())
+}
+
+// Compile and run the test harness of the given Exercise including its bonus
+// tests. Passing them is recorded in the progress state on its own.
+pub fn test_bonus(
+    exercise: &Exercise,
+    verbose: bool,
+    state: &Mutex<ProgressState>,
+) -> Result<(), ()> {
+    if !exercise.has_bonus_tests() {
+        warn!("{} has no bonus tests", exercise);
+        return Err(());
+    }
+    compile_and_test(
+        exercise,
+        RunMode::Bonus,
+        verbose,
+        false,
+        state,
+        &CancelToken::default(),
+    )?;
     Ok(())
 }
 
 // Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+fn compile_only(
+    exercise: &Exercise,
+    success_hints: bool,
+    state: &Mutex<ProgressState>,
+    cancel: &CancelToken,
+) -> Result<bool, ()> {
+    let progress_bar = reporter().spinner(format!("Compiling {exercise}..."));
 
-    let _ = compile(exercise, &progress_bar)?;
+    let outcome = cache::cached(exercise, || {
+        evaluate(exercise, &progress_bar, false, cancel)
+    });
     progress_bar.finish_and_clear();
 
-    Ok(prompt_for_completion(exercise, None, success_hints))
+    match outcome {
+        Outcome::CompileError(output) => warn_compile_error(exercise, &output),
+        Outcome::Cancelled => Err(()),
+        _ => {
+            state.lock().unwrap().record_pass(exercise);
+            Ok(prompt_for_completion(exercise, None, success_hints))
+        }
+    }
 }
 
 // Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(100);
-
-    let compilation = compile(exercise, &progress_bar)?;
+fn compile_and_run_interactively(
+    exercise: &Exercise,
+    success_hints: bool,
+    state: &Mutex<ProgressState>,
+    cancel: &CancelToken,
+) -> Result<bool, ()> {
+    let progress_bar = reporter().spinner(format!("Compiling {exercise}..."));
 
-    progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
+    let outcome = cache::cached(exercise, || evaluate(exercise, &progress_bar, true, cancel));
     progress_bar.finish_and_clear();
 
-    let output = match result {
-        Ok(output) => output,
-        Err(output) => {
-            warn!("Ran {} with errors", exercise);
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
+    let output = match outcome {
+        Outcome::Success(output) => output,
+        Outcome::CompileError(output) => return warn_compile_error(exercise, &output),
+        Outcome::Cancelled => return Err(()),
+        Outcome::RunError(output) => {
+            if output.timed_out {
+                warn_timed_out(exercise);
+            } else {
+                warn!("Ran {} with errors", exercise);
+            }
+            reporter().output(&output.stdout);
+            reporter().output(&output.stderr);
+            print_error_hints(exercise, &output);
             return Err(());
         }
     };
 
-    Ok(prompt_for_completion(exercise, Some(output.stdout), success_hints))
+    state.lock().unwrap().record_pass(exercise);
+    Ok(prompt_for_completion(
+        exercise,
+        Some(output.stdout),
+        success_hints,
+    ))
 }
 
 // Compile the given Exercise as a test harness and display
 // the output if verbose is set to true
-fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Testing {exercise}..."));
-    progress_bar.enable_steady_tick(100);
+fn compile_and_test(
+    exercise: &Exercise,
+    run_mode: RunMode,
+    verbose: bool,
+    success_hints: bool,
+    state: &Mutex<ProgressState>,
+    cancel: &CancelToken,
+) -> Result<bool, ()> {
+    if exercise.tests_changed() {
+        warn_tests_changed(exercise);
+        return Err(());
+    }
+    let progress_bar = reporter().spinner(format!("Testing {exercise}..."));
 
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
+    // Only verifying uses the cache, running a single exercise always builds it afresh
+    let outcome = match run_mode {
+        RunMode::Interactive => {
+            cache::cached(exercise, || evaluate(exercise, &progress_bar, true, cancel))
+        }
+        RunMode::NonInteractive => evaluate(exercise, &progress_bar, true, cancel),
+        RunMode::Bonus => evaluate_bonus(exercise),
+    };
     progress_bar.finish_and_clear();
 
-    match result {
-        Ok(output) => {
+    match outcome {
+        Outcome::Success(output) => {
+            state.lock().unwrap().record_pass(exercise);
+            if let RunMode::Bonus = run_mode {
+                state.lock().unwrap().record_bonus_pass(exercise);
+                success!("Passed the bonus tests of {}!", exercise);
+            }
             if verbose {
-                println!("{}", output.stdout);
+                reporter().output(&output.stdout);
             }
             if let RunMode::Interactive = run_mode {
                 Ok(prompt_for_completion(exercise, None, success_hints))
@@ -112,40 +346,129 @@ fn compile_and_test(exercise: &Exercise, run_mode: RunMode, verbose: bool, succe
                 Ok(true)
             }
         }
-        Err(output) => {
-            warn!(
-                "Testing of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stdout);
+        Outcome::CompileError(output) => warn_compile_error(exercise, &output),
+        Outcome::Cancelled => Err(()),
+        Outcome::RunError(output) => {
+            if output.timed_out {
+                warn_timed_out(exercise);
+            } else if let RunMode::Bonus = run_mode {
+                warn!(
+                    "The bonus tests of {} failed! They don't keep you from moving on. Here's the output:",
+                    exercise
+                );
+            } else {
+                warn!(
+                    "Testing of {} failed! Please try again. Here's the output:",
+                    exercise
+                );
+            }
+            reporter().output(&output.stdout);
+            print_error_hints(exercise, &output);
             Err(())
         }
     }
 }
 
-// Compile the given Exercise and return an object with information
-// about the state of the compilation
-fn compile<'a, 'b>(
-    exercise: &'a Exercise,
-    progress_bar: &'b ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
+// Compile (and run) the exercise like `verify` does, but without printing
+// anything, for callers that show the outcome themselves
+pub fn check(exercise: &Exercise, cancel: &CancelToken) -> Outcome {
+    let run = !matches!(exercise.mode, Mode::Clippy);
+    cache::cached(exercise, || {
+        evaluate(exercise, &ProgressBar::hidden(), run, cancel)
+    })
+}
 
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
-        Err(output) => {
-            progress_bar.finish_and_clear();
-            warn!(
-                "Compiling of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stderr);
-            Err(())
-        }
+// Compile the given Exercise and, if `run` is set, run it.
+// Whatever was cancelled along the way says nothing about the exercise.
+fn evaluate(
+    exercise: &Exercise,
+    progress_bar: &ProgressBar,
+    run: bool,
+    cancel: &CancelToken,
+) -> Outcome {
+    let compilation = match exercise.compile_cancellable(cancel) {
+        _ if cancel.is_cancelled() => return Outcome::Cancelled,
+        Ok(compilation) => compilation,
+        Err(output) => return Outcome::CompileError(output),
+    };
+    if !run {
+        return Outcome::Success(ExerciseOutput::default());
+    }
+    if let Mode::Compile | Mode::Clippy = exercise.mode {
+        progress_bar.set_message(format!("Running {exercise}..."));
+    }
+    match compilation.run() {
+        _ if cancel.is_cancelled() => Outcome::Cancelled,
+        Ok(output) => Outcome::Success(output),
+        Err(output) => Outcome::RunError(output),
+    }
+}
+
+// Compile the given Exercise and run all of its tests, including the bonus ones
+fn evaluate_bonus(exercise: &Exercise) -> Outcome {
+    match exercise.compile() {
+        Ok(compilation) => match compilation.run_with_bonus_tests() {
+            Ok(output) => Outcome::Success(output),
+            Err(output) => Outcome::RunError(output),
+        },
+        Err(output) => Outcome::CompileError(output),
     }
 }
 
-fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, success_hints: bool) -> bool {
+fn warn_compile_error(exercise: &Exercise, output: &ExerciseOutput) -> Result<bool, ()> {
+    if output.timed_out {
+        warn_compile_timed_out(exercise);
+        reporter().output(&output.stderr);
+        return Err(());
+    }
+    warn!(
+        "Compiling of {} failed! Please try again. Here's the output:",
+        exercise
+    );
+    reporter().output(&output.stderr);
+    print_error_hints(exercise, output);
+    Err(())
+}
+
+// Show the hints of info.toml that are about the way the exercise failed
+pub fn print_error_hints(exercise: &Exercise, output: &ExerciseOutput) {
+    for hint in exercise.error_hints_for(output) {
+        reporter().info(&format!("{} {}", style("Hint:").bold(), hint));
+    }
+}
+
+// Tell the user that the exercise only passes because its tests were changed
+fn warn_tests_changed(exercise: &Exercise) {
+    warn!(
+        "The tests of {} were changed! Please solve it without changing the tests, `rustlings reset {}` brings back the original.",
+        exercise,
+        exercise.name
+    );
+}
+
+// Tell the user that the exercise was stopped because it ran for too long
+pub fn warn_timed_out(exercise: &Exercise) {
+    warn!(
+        "{} didn't finish within {} seconds and was stopped! Is there an endless loop?",
+        exercise,
+        exercise.limits().timeout.as_secs()
+    );
+}
+
+// Tell the user that compiling the exercise was stopped because it took too long
+pub fn warn_compile_timed_out(exercise: &Exercise) {
+    warn!(
+        "Compiling {} didn't finish within {} seconds and was stopped! Does its build script loop forever?",
+        exercise,
+        limits::COMPILE_TIMEOUT.as_secs()
+    );
+}
+
+fn prompt_for_completion(
+    exercise: &Exercise,
+    prompt_output: Option<String>,
+    success_hints: bool,
+) -> bool {
     let context = match exercise.state() {
         State::Done => return true,
         State::Pending(context) => context,
@@ -157,7 +480,7 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
         Mode::BuildScript => success!("Successfully compiled {}!", exercise),
     }
 
-    let no_emoji = env::var("NO_EMOJI").is_ok();
+    let no_emoji = !reporter::use_emoji();
 
     let clippy_success_msg = if no_emoji {
         "The code is compiling, and Clippy is happy!"
@@ -171,35 +494,43 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
         Mode::Clippy => clippy_success_msg,
         Mode::BuildScript => "Build script works!",
     };
-    println!();
+    reporter().info("");
     if no_emoji {
-        println!("~*~ {success_msg} ~*~")
+        reporter().info(&format!("~*~ {success_msg} ~*~"))
     } else {
-        println!("🎉 🎉  {success_msg} 🎉 🎉")
+        reporter().info(&format!("🎉 🎉  {success_msg} 🎉 🎉"))
     }
-    println!();
+    reporter().info("");
 
     if let Some(output) = prompt_output {
-        println!("Output:");
-        println!("{}", separator());
-        println!("{output}");
-        println!("{}", separator());
-        println!();
+        reporter().info("Output:");
+        reporter().info(&separator().to_string());
+        reporter().output(&output);
+        reporter().info(&separator().to_string());
+        reporter().info("");
     }
     if success_hints {
-        println!("Hints:");
-        println!("{}", separator());
-        println!("{}", exercise.hint);
-        println!("{}", separator());
-        println!();
+        reporter().info("Hints:");
+        reporter().info(&separator().to_string());
+        reporter().info(&exercise.hint.to_string());
+        reporter().info(&separator().to_string());
+        reporter().info("");
+    }
+
+    if exercise.has_bonus_tests() {
+        reporter().info(&format!(
+            "There are bonus tests as well, `rustlings run --bonus {}` runs them.",
+            exercise.name
+        ));
+        reporter().info("");
     }
 
-    println!("You can keep working on this exercise,");
-    println!(
+    reporter().info("You can keep working on this exercise,");
+    reporter().info(&format!(
         "or jump into the next one by removing the {} comment:",
         style("`I AM NOT DONE`").bold()
-    );
-    println!();
+    ));
+    reporter().info("");
     for context_line in context {
         let formatted_line = if context_line.important {
             format!("{}", style(context_line.line).bold())
@@ -207,12 +538,12 @@ fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, suc
             context_line.line.to_string()
         };
 
-        println!(
+        reporter().info(&format!(
             "{:>2} {}  {}",
             style(context_line.number).blue().bold(),
             style("|").blue(),
             formatted_line
-        );
+        ));
     }
 
     false
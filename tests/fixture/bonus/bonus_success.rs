@@ -0,0 +1,19 @@
+pub fn double(n: i32) -> i32 {
+    n * 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles() {
+        assert_eq!(double(2), 4);
+    }
+
+    #[test]
+    #[ignore = "bonus"]
+    fn doubles_negative_numbers() {
+        assert_eq!(double(-2), -4);
+    }
+}
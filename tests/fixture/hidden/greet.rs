@@ -0,0 +1,13 @@
+pub fn greet(name: &str) -> String {
+    format!("Hello {name}!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greets_by_name() {
+        assert_eq!(greet("Ferris"), "Hello Ferris!");
+    }
+}
@@ -0,0 +1,4 @@
+#[test]
+fn greets_nobody() {
+    assert_eq!(greet(""), "Hello!");
+}
@@ -0,0 +1,4 @@
+#[test]
+fn shouts_nothing() {
+    assert_eq!(shout(""), "");
+}
@@ -0,0 +1,13 @@
+pub fn shout(text: &str) -> String {
+    text.to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shouts_words() {
+        assert_eq!(shout("hello"), "HELLO");
+    }
+}
@@ -0,0 +1,8 @@
+// Tries to get at the key reports are signed with
+fn main() {
+    panic!(
+        "key at runtime: {:?}, key at compile time: {:?}",
+        std::env::var("RUSTLINGS_REPORT_KEY").ok(),
+        option_env!("RUSTLINGS_REPORT_KEY")
+    );
+}
@@ -0,0 +1,4 @@
+#[test]
+fn keeps_its_secret() {
+    assert!(true, "the hidden tests are secret");
+}
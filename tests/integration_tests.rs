@@ -1,10 +1,28 @@
+// The older tests pass their arguments as `&[...]`
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use assert_cmd::prelude::*;
 use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+// A copy of the fixture in a temporary directory, for tests that change
+// files or keep progress, so that they don't interfere with other tests
+fn fixture_copy(fixture: &str, test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustlings_{test}_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for entry in fs::read_dir(Path::new("tests/fixture").join(fixture)).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_file() {
+            fs::copy(&path, dir.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+    dir
+}
+
 #[test]
 fn runs_without_arguments() {
     let mut cmd = Command::cargo_bin("rustlings").unwrap();
@@ -22,32 +40,65 @@ fn fails_when_in_wrong_dir() {
 
 #[test]
 fn verify_all_success() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("success", "verify_all_success");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .arg("verify")
-        .current_dir("tests/fixture/success")
-        .assert()
-        .success();
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert.success();
 }
 
 #[test]
 fn verify_fails_if_some_fails() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("failure", "verify_fails_if_some_fails");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .arg("verify")
-        .current_dir("tests/fixture/failure")
-        .assert()
-        .code(1);
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert.code(1);
+}
+
+#[test]
+fn verify_cached_result_matches() {
+    let dir = fixture_copy("state", "verify_cached");
+    let verify = || {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .arg("verify")
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    let (first, second) = (verify(), verify());
+    let clear = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["cache", "clear"])
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(first.status.code(), Some(1));
+    assert_eq!(first.status, second.status);
+    assert_eq!(first.stdout, second.stdout);
+    clear
+        .success()
+        .stdout(predicates::str::contains("Cleared the cache"));
 }
 
 #[test]
 fn run_single_compile_success() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("success", "run_single_compile_success");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .args(&["run", "compSuccess"])
-        .current_dir("tests/fixture/success/")
-        .assert()
-        .success();
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert.success();
 }
 
 #[test]
@@ -62,12 +113,14 @@ fn run_single_compile_failure() {
 
 #[test]
 fn run_single_test_success() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("success", "run_single_test_success");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .args(&["run", "testSuccess"])
-        .current_dir("tests/fixture/success/")
-        .assert()
-        .success();
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert.success();
 }
 
 #[test]
@@ -80,6 +133,28 @@ fn run_single_test_failure() {
         .code(1);
 }
 
+#[test]
+fn run_single_compile_timeout() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compTimeout"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("didn't finish within 1 seconds"));
+}
+
+#[test]
+fn run_single_compile_timeout_overrides_default() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--timeout", "60", "run", "compTimeout"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("didn't finish within 1 seconds"));
+}
+
 #[test]
 fn run_single_test_not_passed() {
     Command::cargo_bin("rustlings")
@@ -133,13 +208,14 @@ fn reset_no_exercise() {
 
 #[test]
 fn get_hint_for_single_test() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("failure", "single_hint");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .args(&["hint", "testFailure"])
-        .current_dir("tests/fixture/failure")
-        .assert()
-        .code(0)
-        .stdout("Hello!\n");
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert.code(0).stdout("Hello!\n");
 }
 
 #[test]
@@ -169,44 +245,56 @@ fn all_exercises_require_confirmation() {
 
 #[test]
 fn run_compile_exercise_does_not_prompt() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("state", "run_compile_exercise_does_not_prompt");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .args(&["run", "pending_exercise"])
-        .current_dir("tests/fixture/state")
-        .assert()
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert
         .code(0)
         .stdout(predicates::str::contains("I AM NOT DONE").not());
 }
 
 #[test]
 fn run_test_exercise_does_not_prompt() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("state", "run_test_exercise_does_not_prompt");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .args(&["run", "pending_test_exercise"])
-        .current_dir("tests/fixture/state")
-        .assert()
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert
         .code(0)
         .stdout(predicates::str::contains("I AM NOT DONE").not());
 }
 
 #[test]
 fn run_single_test_success_with_output() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("success", "run_single_test_success_with_output");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .args(&["--nocapture", "run", "testSuccess"])
-        .current_dir("tests/fixture/success/")
-        .assert()
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert
         .code(0)
         .stdout(predicates::str::contains("THIS TEST TOO SHALL PASS"));
 }
 
 #[test]
 fn run_single_test_success_without_output() {
-    Command::cargo_bin("rustlings")
+    let dir = fixture_copy("success", "run_single_test_success_without_output");
+    let assert = Command::cargo_bin("rustlings")
         .unwrap()
         .args(&["run", "testSuccess"])
-        .current_dir("tests/fixture/success/")
-        .assert()
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert
         .code(0)
         .stdout(predicates::str::contains("THIS TEST TOO SHALL PASS").not());
 }
@@ -225,7 +313,7 @@ fn run_rustlings_list() {
 fn run_rustlings_list_no_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(&["--legacy-marker", "list"])
         .current_dir("tests/fixture/success")
         .assert()
         .success()
@@ -236,7 +324,7 @@ fn run_rustlings_list_no_pending() {
 fn run_rustlings_list_both_done_and_pending() {
     Command::cargo_bin("rustlings")
         .unwrap()
-        .args(&["list"])
+        .args(&["--legacy-marker", "list"])
         .current_dir("tests/fixture/state")
         .assert()
         .success()
@@ -264,3 +352,600 @@ fn run_rustlings_list_without_done() {
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn run_rustlings_list_uses_verified_progress() {
+    let dir = fixture_copy("success", "progress");
+    let list = || {
+        let output = Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["list", "--unsolved", "--names"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // Nothing counts as done before it was verified, even without `I AM NOT DONE`
+    assert!(list().contains("compSuccess"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert!(!list().contains("compSuccess"));
+    // Changing an exercise after it passed makes it pending again
+    let mut source = fs::read_to_string(dir.join("compSuccess.rs")).unwrap();
+    source += "\n// changed\n";
+    fs::write(dir.join("compSuccess.rs"), source).unwrap();
+    let unsolved = list();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(unsolved.contains("compSuccess"));
+    assert!(!unsolved.contains("testSuccess"));
+}
+
+#[test]
+fn reset_restores_original_and_undo_brings_it_back() {
+    let dir = std::env::temp_dir().join(format!("rustlings_reset_{}", std::process::id()));
+    fs::create_dir_all(dir.join("exercises/intro")).unwrap();
+    fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"intro2\"\npath = \"exercises/intro/intro2.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    let exercise = dir.join("exercises/intro/intro2.rs");
+    let original = fs::read_to_string("exercises/intro/intro2.rs").unwrap();
+    // Changed before rustlings ever ran, the original still comes back
+    let changed = format!("{original}\n// my solution\n");
+    fs::write(&exercise, &changed).unwrap();
+
+    let reset = rustlings(&["reset", "intro2"]);
+    let after_reset = fs::read_to_string(&exercise).unwrap();
+    let undo = rustlings(&["reset", "--undo", "intro2"]);
+    let after_undo = fs::read_to_string(&exercise).unwrap();
+    let second_undo = rustlings(&["reset", "--undo", "intro2"]);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(reset.status.success());
+    assert_eq!(after_reset, original);
+    assert!(undo.status.success());
+    assert_eq!(after_undo, changed);
+    assert!(!second_undo.status.success());
+}
+
+#[test]
+fn reset_restores_the_original_after_rebuilding_with_a_solved_exercise() {
+    let dir = std::env::temp_dir().join(format!("rustlings_rebuild_{}", std::process::id()));
+    // A student's checkout: the exercise is solved, and rustlings is built in it
+    for source in glob("src/*.rs")
+        .unwrap()
+        .chain(glob(".rustlings-originals/**/*").unwrap())
+    {
+        let source = source.unwrap();
+        if source.is_file() {
+            fs::create_dir_all(dir.join(source.parent().unwrap())).unwrap();
+            fs::copy(&source, dir.join(&source)).unwrap();
+        }
+    }
+    for file in ["Cargo.toml", "Cargo.lock", "build.rs", "info.toml"] {
+        if Path::new(file).exists() {
+            fs::copy(file, dir.join(file)).unwrap();
+        }
+    }
+    fs::create_dir_all(dir.join("exercises/intro")).unwrap();
+    let original = fs::read_to_string(".rustlings-originals/exercises/intro/intro2.rs").unwrap();
+    let solved = format!("{original}\n// my solution\n");
+    let exercise = dir.join("exercises/intro/intro2.rs");
+    fs::write(&exercise, &solved).unwrap();
+
+    // Kept between runs, so the dependencies are only built once
+    let target_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/rebuild");
+    let build = Command::new(env!("CARGO"))
+        .args(["build", "--offline", "--quiet"])
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let reset =
+        Command::new(target_dir.join(format!("debug/rustlings{}", std::env::consts::EXE_SUFFIX)))
+            .args(["reset", "intro2"])
+            .current_dir(&dir)
+            .output();
+    let after_reset = fs::read_to_string(&exercise).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(
+        build.status.success(),
+        "{}",
+        String::from_utf8_lossy(&build.stderr)
+    );
+    assert!(reset.unwrap().status.success());
+    assert_eq!(after_reset, original);
+}
+
+#[test]
+fn changed_tests_are_reported() {
+    let dir = fixture_copy("failure", "changed_tests");
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    // Weakening the test makes it pass, but the fingerprint catches it
+    fs::write(dir.join("testFailure.rs"), "#[test]\nfn passing() {}\n").unwrap();
+    let run = rustlings(&["run", "testFailure"]);
+    let cicv = rustlings(&["cicvverify", "-o", "result.json"]);
+    let result = fs::read_to_string(dir.join("result.json")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(!run.status.success());
+    assert!(String::from_utf8(run.stdout)
+        .unwrap()
+        .contains("The tests of testFailure.rs were changed"));
+    assert!(cicv.status.success());
+    assert!(result.contains("\"failure\": \"tests_modified\""));
+}
+
+#[test]
+fn hidden_tests_are_graded_separately() {
+    let output = std::env::temp_dir().join(format!("rustlings_hidden_{}.json", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("cicvverify")
+        .arg("-o")
+        .arg(&output)
+        .args(["--hidden-tests", "hidden_tests"])
+        .current_dir("tests/fixture/hidden")
+        .assert()
+        .success();
+    let result = fs::read_to_string(&output).unwrap();
+    fs::remove_file(&output).unwrap();
+
+    // greet passes its own tests, but not the hidden ones
+    assert!(result.contains("\"total_succeeds\": 1"));
+    assert!(result.contains("rustlings_hidden_tests::greets_nobody"));
+    assert!(!result.contains("Hello!"));
+    // ... which earns it half of its weight of 2
+    let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+    let exercises = result["exercises"].as_array().unwrap();
+    let greet = exercises.iter().find(|e| e["name"] == "greet").unwrap();
+    assert_eq!(greet["score"], 1.0);
+    assert_eq!(greet["max_score"], 2);
+    assert_eq!(result["statistics"]["score"], 2.0);
+    assert_eq!(result["statistics"]["max_score"], 3);
+}
+
+#[test]
+fn exercises_cant_read_hidden_tests() {
+    let output = std::env::temp_dir().join(format!("rustlings_snoop_{}.json", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("cicvverify")
+        .arg("-o")
+        .arg(&output)
+        .args(["--hidden-tests", "hidden_tests"])
+        .current_dir("tests/fixture/snoop")
+        .assert()
+        .success();
+    let result = fs::read_to_string(&output).unwrap();
+    fs::remove_file(&output).unwrap();
+
+    assert!(result.contains("\"prints_hidden_tests\""));
+    assert!(!result.contains("secret"));
+}
+
+#[test]
+fn bonus_tests_only_run_when_asked_for() {
+    let dir = fixture_copy("bonus", "bonus");
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    let run = rustlings(&["run", "bonus_failure"]);
+    let failed_bonus = rustlings(&["run", "--bonus", "bonus_failure"]);
+    let passed_bonus = rustlings(&["run", "--bonus", "bonus_success"]);
+    let state = fs::read_to_string(dir.join(".rustlings/state.json")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(run.status.success());
+    assert!(!failed_bonus.status.success());
+    assert!(String::from_utf8(failed_bonus.stdout)
+        .unwrap()
+        .contains("test tests::rounds_up ... FAILED"));
+    assert!(passed_bonus.status.success());
+    let (_, bonus_passed) = state.split_once("\"bonus_passed\"").unwrap();
+    assert!(bonus_passed.contains("bonus_success") && !bonus_passed.contains("bonus_failure"));
+}
+
+#[test]
+fn bonus_tests_are_graded_separately() {
+    let output = std::env::temp_dir().join(format!("rustlings_bonus_{}.json", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("cicvverify")
+        .arg("-o")
+        .arg(&output)
+        .current_dir("tests/fixture/bonus")
+        .assert()
+        .success();
+    let result = fs::read_to_string(&output).unwrap();
+    fs::remove_file(&output).unwrap();
+
+    assert!(result.contains("\"total_succeeds\": 2"));
+    // Only the bonus tests of bonus_failure fail
+    assert!(result.contains("\"tests::rounds_up\""));
+}
+
+#[test]
+fn get_all_hints_for_single_test() {
+    let dir = fixture_copy("failure", "all_hints");
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "--all", "compFailure"])
+        .current_dir(&dir)
+        .assert();
+    fs::remove_dir_all(&dir).unwrap();
+    assert.code(0).stdout(
+        predicates::str::contains("First hint")
+            .and(predicates::str::contains("Second hint"))
+            .and(predicates::str::contains("again").not()),
+    );
+}
+
+#[test]
+fn hints_are_revealed_one_at_a_time() {
+    let dir = fixture_copy("failure", "hints");
+    let hint = || {
+        let output = Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["hint", "compFailure"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let (first, second, third) = (hint(), hint(), hint());
+    let hints = fs::read_to_string(dir.join(".rustlings-hints.json")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(first.contains("First hint") && !first.contains("Second hint"));
+    assert!(first.contains("rustlings hint compFailure` again"));
+    assert!(second.contains("First hint") && second.contains("Second hint"));
+    assert!(!second.contains("again"));
+    assert_eq!(second, third);
+    assert!(hints.contains("\"compFailure\": 2"));
+}
+
+#[test]
+fn hints_reach_the_report_without_the_local_state() {
+    let dir = fixture_copy("failure", "hints_report");
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    assert!(rustlings(&["hint", "compFailure"]).status.success());
+    // A fresh checkout in CI only has what was committed
+    fs::remove_dir_all(dir.join(".rustlings")).unwrap();
+    let cicv = rustlings(&["cicvverify", "-o", "result.json"]);
+    let result = fs::read_to_string(dir.join("result.json")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(cicv.status.success());
+    assert!(result.contains("\"hints_revealed\": 1"));
+}
+
+#[test]
+fn check_info_accepts_failing_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("check-info")
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .success();
+}
+
+#[test]
+fn check_info_reports_exercises_that_already_pass() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("check-info")
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "info.toml:1: compSuccess already passes",
+        ));
+}
+
+#[test]
+fn check_info_reports_duplicates_and_missing_files() {
+    let dir = fixture_copy("failure", "check_info");
+    let mut info = fs::read_to_string(dir.join("info.toml")).unwrap();
+    info += "\n[[exercises]]\nname = \"testFailure\"\npath = \"missing.rs\"\nmode = \"test\"\nhint = \"\"\n";
+    fs::write(dir.join("info.toml"), info).unwrap();
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("check-info")
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("info.toml:21: the name testFailure is already used on line 7"));
+    assert!(stdout.contains("info.toml:21: missing.rs doesn't exist"));
+}
+
+#[test]
+fn check_info_takes_fingerprints_from_the_originals() {
+    let dir = std::env::temp_dir().join(format!("rustlings_fingerprints_{}", std::process::id()));
+    fs::create_dir_all(dir.join("exercises/structs")).unwrap();
+    fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"structs3\"\npath = \"exercises/structs/structs3.rs\"\nmode = \"test\"\ntest_fingerprint = \"73444a8d9ceacdff1c58282ac83094c0e07b8061a25a9ea02227b019269a6360\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    // The tests in this checkout were changed, the ones rustlings was built with weren't
+    let original = fs::read_to_string("exercises/structs/structs3.rs").unwrap();
+    fs::write(
+        dir.join("exercises/structs/structs3.rs"),
+        original.replace("assert_eq!", "assert_ne!"),
+    )
+    .unwrap();
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("check-info")
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("test_fingerprint"));
+}
+
+#[test]
+fn error_hints_are_shown_for_failed_tests() {
+    let dir = fixture_copy("failure", "error_hints");
+    let mut info = fs::read_to_string(dir.join("info.toml")).unwrap();
+    info += "\n[[exercises]]\nname = \"testNotPassed\"\npath = \"testNotPassed.rs\"\nmode = \"test\"\nhint = \"\"\n\n[exercises.error_hints]\nnot_passing = \"Make the assertion hold.\"\n";
+    fs::write(dir.join("info.toml"), info).unwrap();
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testNotPassed"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("Hint: Make the assertion hold."));
+}
+
+#[test]
+fn invalid_info_toml_is_reported() {
+    let dir = fixture_copy("success", "invalid_info");
+    fs::write(dir.join("info.toml"), "[[exercises]]\nname = \"x\"\n").unwrap();
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("info.toml is invalid"));
+    assert!(stdout.contains("line 1"));
+}
+
+#[test]
+fn run_rustlings_list_by_tag() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--tag", "Basics"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("pending_exercise")
+                .and(predicates::str::contains("finished_exercise").not()),
+        );
+}
+
+#[test]
+fn run_rustlings_list_by_difficulty() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--difficulty", "hard"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("finished_exercise")
+                .and(predicates::str::contains("pending_exercise").not()),
+        );
+}
+
+#[test]
+fn every_exercise_has_tags_difficulty_and_estimated_time() {
+    let info: toml::Value = toml::from_str(&fs::read_to_string("info.toml").unwrap()).unwrap();
+    for exercise in info["exercises"].as_array().unwrap() {
+        let name = exercise["name"].as_str().unwrap();
+        for key in ["tags", "difficulty", "estimated_minutes"] {
+            assert!(exercise.get(key).is_some(), "{name} has no {key}");
+        }
+    }
+}
+
+#[test]
+fn run_rustlings_list_unknown_difficulty() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--difficulty", "impossible"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn run_rustlings_list_summary() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--legacy-marker", "list", "--summary"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("1/3")
+                .and(predicates::str::contains("5 min"))
+                .and(predicates::str::contains("pending_exercise").not()),
+        );
+}
+
+#[test]
+fn book_shows_sections() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["book", "pending_exercise"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("§3.1 Variables and Mutability").and(
+                predicates::str::contains("ch03-01-variables-and-mutability.html"),
+            ),
+        );
+}
+
+#[test]
+fn plain_format_has_no_colors_or_emoji() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--format", "plain", "run", "compFailure"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("Warning: Compilation of compFailure.rs failed!")
+                .and(predicates::str::contains("\x1b[").not()),
+        );
+}
+
+#[test]
+fn json_format_reports_one_event_per_line() {
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--format", "json", "list", "--names"])
+        .current_dir("tests/fixture/state")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let events: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(events
+        .iter()
+        .any(|event| event["event"] == "message" && event["text"] == "pending_exercise"));
+}
+
+// The JSON events `verify --message-format=json` printed
+fn verify_events(dir: &Path) -> Vec<serde_json::Value> {
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--message-format=json"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn verify_json_reports_compile_errors() {
+    let dir = fixture_copy("failure", "verify_json_errors");
+    let events = verify_events(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+    let names: Vec<&str> = events
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "exercise-started",
+            "compile-finished",
+            "exercise-failed",
+            "progress"
+        ]
+    );
+    assert_eq!(events[1]["success"], false);
+    assert_eq!(events[1]["diagnostics"][0]["spans"][0]["line_start"], 3);
+    assert_eq!(events[2]["reason"], "compile-error");
+    assert_eq!(events[3]["done"], 0);
+    assert_eq!(events[3]["total"], 3);
+}
+
+#[test]
+fn verify_json_reports_where_the_marker_is() {
+    let dir = fixture_copy("state", "verify_json");
+    let events = verify_events(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+    let passed = events
+        .iter()
+        .find(|e| e["event"] == "exercise-passed")
+        .unwrap();
+    assert_eq!(passed["exercise"], "pending_exercise");
+    assert_eq!(passed["done"], false);
+    assert_eq!(passed["marker"]["line"], 3);
+    assert_eq!(passed["marker"]["column"], 4);
+}
+
+#[test]
+fn report_key_never_reaches_the_report() {
+    let output =
+        std::env::temp_dir().join(format!("rustlings_secrets_{}.json", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("cicvverify")
+        .arg("-o")
+        .arg(&output)
+        .env("RUSTLINGS_REPORT_KEY", "topsecret")
+        .current_dir("tests/fixture/secrets")
+        .assert()
+        .success();
+    let result = fs::read_to_string(&output).unwrap();
+    fs::remove_file(&output).unwrap();
+
+    assert!(result.contains("key at runtime: None, key at compile time: None"));
+    assert!(!result.contains("topsecret"));
+}